@@ -0,0 +1,91 @@
+use anyhow::{anyhow, Result};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    transaction::Transaction,
+};
+
+use crate::helpers::{
+    counter::{get_config_address, get_counter_address, get_delegate_address, get_program_id},
+    error::decode_client_error,
+    transaction::{fetch_transaction_output, print_events},
+};
+
+pub async fn approve_delegate(
+    keypair: Keypair,
+    rpc_url: String,
+    seed: Vec<u8>,
+    delegate: Pubkey,
+    allowance: u64,
+    expiry_slot: Option<u64>,
+) -> Result<()> {
+    let client = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
+    let program_id = get_program_id();
+
+    let authority = keypair.pubkey();
+    let (counter_pubkey, _) = get_counter_address(&authority, &seed, &program_id);
+    let (config_pubkey, _) = get_config_address(&program_id);
+    let (delegate_pubkey, _) = get_delegate_address(&counter_pubkey, &delegate, &program_id);
+
+    println!("Authority: {}", authority);
+    println!("Counter address: {}", counter_pubkey);
+    println!("Delegate: {}", delegate);
+    println!("Delegate account: {}", delegate_pubkey);
+    println!("Allowance: {}", allowance);
+    if let Some(expiry_slot) = expiry_slot {
+        println!("Expiry slot: {}", expiry_slot);
+    }
+
+    // Check if counter exists
+    client.get_account(&counter_pubkey).map_err(|_| {
+        anyhow!("Counter does not exist. Create it first with the 'create' command.")
+    })?;
+
+    // Prepare instruction data: discriminator (13) + delegate (32 bytes)
+    // + allowance (8 bytes) + expiry slot (8 bytes, 0 never expires)
+    let mut instruction_data = vec![13]; // ApproveDelegate discriminator
+    instruction_data.extend_from_slice(delegate.as_ref());
+    instruction_data.extend_from_slice(&allowance.to_le_bytes());
+    instruction_data.extend_from_slice(&expiry_slot.unwrap_or(0).to_le_bytes());
+
+    // Create instruction
+    let approve_instruction = Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(counter_pubkey, false),
+            AccountMeta::new(authority, true),
+            AccountMeta::new_readonly(config_pubkey, false),
+            AccountMeta::new(delegate_pubkey, false),
+            // The authority pays for the delegate account
+            AccountMeta::new(authority, true),
+            AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
+        ],
+        data: instruction_data,
+    };
+
+    // Get recent blockhash
+    let recent_blockhash = client.get_latest_blockhash()?;
+
+    // Create and send transaction
+    let transaction = Transaction::new_signed_with_payer(
+        &[approve_instruction],
+        Some(&authority),
+        &[&keypair],
+        recent_blockhash,
+    );
+
+    let signature = client
+        .send_and_confirm_transaction(&transaction)
+        .map_err(decode_client_error)?;
+    println!("✅ Delegate approved successfully!");
+    println!("Transaction signature: {}", signature);
+
+    let output = fetch_transaction_output(&client, &signature)?;
+    print_events(&output.events);
+
+    Ok(())
+}
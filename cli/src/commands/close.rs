@@ -0,0 +1,83 @@
+use anyhow::{anyhow, Result};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    transaction::Transaction,
+};
+
+use crate::helpers::{
+    counter::{get_config_address, get_counter_address, get_history_address, get_program_id},
+    error::decode_client_error,
+    transaction::{fetch_transaction_output, print_events},
+};
+
+pub async fn close_counter(
+    keypair: Keypair,
+    rpc_url: String,
+    seed: Vec<u8>,
+    recipient: Option<Pubkey>,
+) -> Result<()> {
+    let client = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
+    let program_id = get_program_id();
+
+    let authority = keypair.pubkey();
+    let (counter_pubkey, _) = get_counter_address(&authority, &seed, &program_id);
+    let (config_pubkey, _) = get_config_address(&program_id);
+    // Rent goes back to the authority unless another recipient is given
+    let recipient = recipient.unwrap_or(authority);
+
+    println!("Authority: {}", authority);
+    println!("Counter address: {}", counter_pubkey);
+    println!("Recipient: {}", recipient);
+
+    // Check if counter exists
+    client.get_account(&counter_pubkey).map_err(|_| {
+        anyhow!("Counter does not exist. Create it first with the 'create' command.")
+    })?;
+
+    let mut accounts = vec![
+        AccountMeta::new(counter_pubkey, false),
+        AccountMeta::new_readonly(authority, true),
+        AccountMeta::new_readonly(config_pubkey, false),
+        AccountMeta::new(recipient, false),
+    ];
+
+    // The history is closed along with the counter
+    let (history_pubkey, _) = get_history_address(&counter_pubkey, &program_id);
+    if client.get_account(&history_pubkey).is_ok() {
+        accounts.push(AccountMeta::new(history_pubkey, false));
+    }
+
+    // Create instruction
+    let close_instruction = Instruction {
+        program_id,
+        accounts,
+        data: vec![5], // Close discriminator
+    };
+
+    // Get recent blockhash
+    let recent_blockhash = client.get_latest_blockhash()?;
+
+    // Create and send transaction
+    let transaction = Transaction::new_signed_with_payer(
+        &[close_instruction],
+        Some(&authority),
+        &[&keypair],
+        recent_blockhash,
+    );
+
+    let signature = client
+        .send_and_confirm_transaction(&transaction)
+        .map_err(decode_client_error)?;
+    println!("✅ Counter closed successfully!");
+    println!("Transaction signature: {}", signature);
+
+    let output = fetch_transaction_output(&client, &signature)?;
+    print_events(&output.events);
+
+    Ok(())
+}
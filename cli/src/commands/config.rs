@@ -0,0 +1,145 @@
+use anyhow::{anyhow, Result};
+use counter_pinocchio::{helpers::ProgramDataAccount, state::Config};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    transaction::Transaction,
+};
+
+use crate::helpers::{
+    counter::{format_limit, get_config_address, get_program_id},
+    error::decode_client_error,
+};
+
+pub async fn initialize_config(
+    keypair: Keypair,
+    rpc_url: String,
+    admin: Option<Pubkey>,
+    default_max_step: u64,
+) -> Result<()> {
+    let client = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
+    let program_id = get_program_id();
+
+    // Only the upgrade authority may create the config
+    let upgrade_authority = keypair.pubkey();
+    let admin = admin.unwrap_or(upgrade_authority);
+    let (config_pubkey, _) = get_config_address(&program_id);
+    let loader_id = Pubkey::new_from_array(ProgramDataAccount::LOADER_ID);
+    let (program_data, _) = Pubkey::find_program_address(&[program_id.as_ref()], &loader_id);
+
+    println!("Upgrade authority: {}", upgrade_authority);
+    println!("Config address: {}", config_pubkey);
+    println!("Admin: {}", admin);
+
+    // Prepare instruction data: discriminator (17) + admin (32 bytes) + default max step (8 bytes)
+    let mut instruction_data = vec![17]; // InitializeConfig discriminator
+    instruction_data.extend_from_slice(admin.as_ref());
+    instruction_data.extend_from_slice(&default_max_step.to_le_bytes());
+
+    // Create instruction
+    let initialize_instruction = Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(config_pubkey, false),
+            AccountMeta::new(upgrade_authority, true),
+            AccountMeta::new_readonly(program_data, false),
+            AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
+        ],
+        data: instruction_data,
+    };
+
+    // Get recent blockhash
+    let recent_blockhash = client.get_latest_blockhash()?;
+
+    // Create and send transaction
+    let transaction = Transaction::new_signed_with_payer(
+        &[initialize_instruction],
+        Some(&upgrade_authority),
+        &[&keypair],
+        recent_blockhash,
+    );
+
+    let signature = client
+        .send_and_confirm_transaction(&transaction)
+        .map_err(decode_client_error)?;
+    println!("✅ Config initialized successfully!");
+    println!("Transaction signature: {}", signature);
+
+    Ok(())
+}
+
+/// Replaces the given fields of the config, keeping the others.
+pub async fn update_config(
+    keypair: Keypair,
+    rpc_url: String,
+    new_admin: Option<Pubkey>,
+    paused: Option<bool>,
+    default_max_step: Option<u64>,
+) -> Result<()> {
+    let client = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
+    let program_id = get_program_id();
+
+    let admin = keypair.pubkey();
+    let (config_pubkey, _) = get_config_address(&program_id);
+
+    println!("Admin: {}", admin);
+    println!("Config address: {}", config_pubkey);
+
+    // Fetch the current config to keep the fields that are not updated
+    let account = client.get_account(&config_pubkey).map_err(|_| {
+        anyhow!("Config does not exist. Create it first with the 'init-config' command.")
+    })?;
+    let config = Config::load(&account.data)
+        .map_err(|e| anyhow!("Failed to deserialize config data: {:?}", e))?;
+
+    let new_admin = new_admin.unwrap_or(Pubkey::new_from_array(config.admin));
+    let paused = paused.unwrap_or(config.is_paused());
+    let default_max_step = default_max_step.unwrap_or(config.default_max_step());
+
+    // Prepare instruction data: discriminator (18) + admin (32 bytes) + paused (1 byte) + default max step (8 bytes)
+    let mut instruction_data = vec![18]; // UpdateConfig discriminator
+    instruction_data.extend_from_slice(new_admin.as_ref());
+    instruction_data.push(paused as u8);
+    instruction_data.extend_from_slice(&default_max_step.to_le_bytes());
+
+    // Create instruction
+    let update_instruction = Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(config_pubkey, false),
+            AccountMeta::new_readonly(admin, true),
+        ],
+        data: instruction_data,
+    };
+
+    // Get recent blockhash
+    let recent_blockhash = client.get_latest_blockhash()?;
+
+    // Create and send transaction
+    let transaction = Transaction::new_signed_with_payer(
+        &[update_instruction],
+        Some(&admin),
+        &[&keypair],
+        recent_blockhash,
+    );
+
+    let signature = client
+        .send_and_confirm_transaction(&transaction)
+        .map_err(decode_client_error)?;
+    println!("✅ Config updated successfully!");
+    println!("Transaction signature: {}", signature);
+    println!();
+    println!("⚙️  Config:");
+    println!("  Admin: {}", new_admin);
+    println!("  Paused: {}", paused);
+    println!(
+        "  Default max step: {}",
+        format_limit(default_max_step, u64::MAX)
+    );
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use anyhow::{anyhow, Result};
+use counter_pinocchio::state::Limits;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::{AccountMeta, Instruction},
+    signature::Keypair,
+    signer::Signer,
+    transaction::Transaction,
+};
+
+use crate::helpers::{
+    counter::{get_config_address, get_counter_address, get_program_id},
+    error::decode_client_error,
+    transaction::{fetch_transaction_output, print_events},
+};
+
+pub async fn configure_counter(
+    keypair: Keypair,
+    rpc_url: String,
+    seed: Vec<u8>,
+    limits: Limits,
+) -> Result<()> {
+    let client = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
+    let program_id = get_program_id();
+
+    let authority = keypair.pubkey();
+    let (counter_pubkey, _) = get_counter_address(&authority, &seed, &program_id);
+    let (config_pubkey, _) = get_config_address(&program_id);
+
+    println!("Authority: {}", authority);
+    println!("Counter address: {}", counter_pubkey);
+
+    // Check if counter exists
+    client.get_account(&counter_pubkey).map_err(|_| {
+        anyhow!("Counter does not exist. Create it first with the 'create' command.")
+    })?;
+
+    // Prepare instruction data: discriminator (10) + min, max, max_step (8 bytes each)
+    let mut instruction_data = vec![10]; // Configure discriminator
+    instruction_data.extend_from_slice(&limits.to_bytes());
+
+    // Create instruction
+    let configure_instruction = Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(counter_pubkey, false),
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new_readonly(config_pubkey, false),
+        ],
+        data: instruction_data,
+    };
+
+    // Get recent blockhash
+    let recent_blockhash = client.get_latest_blockhash()?;
+
+    // Create and send transaction
+    let transaction = Transaction::new_signed_with_payer(
+        &[configure_instruction],
+        Some(&authority),
+        &[&keypair],
+        recent_blockhash,
+    );
+
+    let signature = client
+        .send_and_confirm_transaction(&transaction)
+        .map_err(decode_client_error)?;
+    println!("✅ Counter limits updated successfully!");
+    println!("Transaction signature: {}", signature);
+
+    let output = fetch_transaction_output(&client, &signature)?;
+    print_events(&output.events);
+
+    Ok(())
+}
@@ -1,4 +1,6 @@
 use anyhow::{anyhow, Result};
+use clap::ValueEnum;
+use counter_pinocchio::state::Limits;
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::{
     commitment_config::CommitmentConfig,
@@ -8,32 +10,80 @@ use solana_sdk::{
     transaction::Transaction,
 };
 
-use crate::helpers::counter::{get_counter_address, get_program_id};
+use crate::helpers::{
+    counter::{create_instruction_data, get_config_address, get_counter_address, get_program_id},
+    error::decode_client_error,
+    transaction::{fetch_transaction_output, print_events},
+};
+
+/// Kind of counter account to create.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum CounterKind {
+    /// `u64` value with optional limits
+    Unsigned,
+    /// `i64` value that may go below zero
+    Signed,
+    /// `u128` value
+    Wide,
+}
+
+impl CounterKind {
+    fn create_discriminator(self) -> u8 {
+        match self {
+            CounterKind::Unsigned => 0,
+            CounterKind::Signed => 20,
+            CounterKind::Wide => 21,
+        }
+    }
+}
+
+pub async fn create_counter(
+    keypair: Keypair,
+    rpc_url: String,
+    seed: Vec<u8>,
+    kind: CounterKind,
+    limits: Option<Limits>,
+) -> Result<()> {
+    if kind != CounterKind::Unsigned && limits.is_some() {
+        return Err(anyhow!("Only unsigned counters have limits"));
+    }
 
-pub async fn create_counter(keypair: Keypair, rpc_url: String) -> Result<()> {
     let client = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
     let program_id = get_program_id();
 
     let authority = keypair.pubkey();
-    let (counter_pubkey, _) = get_counter_address(&authority, &program_id);
+    let (counter_pubkey, _) = get_counter_address(&authority, &seed, &program_id);
+    let (config_pubkey, _) = get_config_address(&program_id);
 
     println!("Authority: {}", authority);
     println!("Counter address: {}", counter_pubkey);
 
-    // Check if counter already exists
-    if let Ok(_account) = client.get_account(&counter_pubkey) {
-        return Err(anyhow!("Counter already exists for this authority"));
+    // Check if counter already exists. A PDA that only holds lamports sent
+    // ahead of time is still created by the program.
+    if let Ok(account) = client.get_account(&counter_pubkey) {
+        if account.owner == program_id || !account.data.is_empty() {
+            return Err(anyhow!("Counter already exists for this authority"));
+        }
+        println!(
+            "Counter address is pre-funded with {} lamports",
+            account.lamports
+        );
     }
 
+    // Prepare instruction data: discriminator (0, 20 or 21) + counter seed + limits
+    let instruction_data =
+        create_instruction_data(kind.create_discriminator(), &seed, limits.as_ref());
+
     // Create instruction
     let create_instruction = Instruction {
         program_id,
         accounts: vec![
             AccountMeta::new(counter_pubkey, false),
             AccountMeta::new(authority, true),
+            AccountMeta::new_readonly(config_pubkey, false),
             AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
         ],
-        data: vec![0], // Create discriminator
+        data: instruction_data,
     };
 
     // Get recent blockhash
@@ -47,9 +97,14 @@ pub async fn create_counter(keypair: Keypair, rpc_url: String) -> Result<()> {
         recent_blockhash,
     );
 
-    let signature = client.send_and_confirm_transaction(&transaction)?;
+    let signature = client
+        .send_and_confirm_transaction(&transaction)
+        .map_err(decode_client_error)?;
     println!("✅ Counter created successfully!");
     println!("Transaction signature: {}", signature);
 
+    let output = fetch_transaction_output(&client, &signature)?;
+    print_events(&output.events);
+
     Ok(())
 }
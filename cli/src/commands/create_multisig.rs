@@ -0,0 +1,80 @@
+use anyhow::{anyhow, Result};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    transaction::Transaction,
+};
+
+use crate::helpers::{
+    counter::{get_multisig_address, get_program_id},
+    error::decode_client_error,
+};
+
+pub async fn create_multisig(
+    keypair: Keypair,
+    rpc_url: String,
+    seed: Vec<u8>,
+    threshold: u8,
+    signers: Vec<Pubkey>,
+) -> Result<()> {
+    let client = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
+    let program_id = get_program_id();
+
+    let creator = keypair.pubkey();
+    let (multisig_pubkey, _) = get_multisig_address(&creator, &seed, &program_id);
+
+    println!("Creator: {}", creator);
+    println!("Multisig address: {}", multisig_pubkey);
+    println!("Threshold: {} of {}", threshold, signers.len());
+
+    // Check if the multisig already exists
+    if client.get_account(&multisig_pubkey).is_ok() {
+        return Err(anyhow!("Multisig already exists at {}", multisig_pubkey));
+    }
+
+    // Prepare instruction data: discriminator (19) + threshold (1 byte) + seed
+    let mut instruction_data = vec![19, threshold]; // InitializeMultisig discriminator
+    instruction_data.extend_from_slice(&seed);
+
+    // Members follow the fixed accounts and do not need to sign
+    let mut accounts = vec![
+        AccountMeta::new(multisig_pubkey, false),
+        AccountMeta::new(creator, true),
+        AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
+    ];
+    accounts.extend(
+        signers
+            .iter()
+            .map(|signer| AccountMeta::new_readonly(*signer, false)),
+    );
+
+    // Create instruction
+    let create_instruction = Instruction {
+        program_id,
+        accounts,
+        data: instruction_data,
+    };
+
+    // Get recent blockhash
+    let recent_blockhash = client.get_latest_blockhash()?;
+
+    // Create and send transaction
+    let transaction = Transaction::new_signed_with_payer(
+        &[create_instruction],
+        Some(&creator),
+        &[&keypair],
+        recent_blockhash,
+    );
+
+    let signature = client
+        .send_and_confirm_transaction(&transaction)
+        .map_err(decode_client_error)?;
+    println!("✅ Multisig created successfully!");
+    println!("Transaction signature: {}", signature);
+
+    Ok(())
+}
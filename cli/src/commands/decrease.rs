@@ -0,0 +1,91 @@
+use anyhow::{anyhow, Result};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::{AccountMeta, Instruction},
+    signature::Keypair,
+    signer::Signer,
+    transaction::Transaction,
+};
+
+use crate::helpers::{
+    counter::{get_config_address, get_counter_address, get_history_address, get_program_id},
+    error::decode_client_error,
+    transaction::{fetch_transaction_output, print_events, print_value_change},
+};
+
+pub async fn decrease_counter(
+    keypair: Keypair,
+    rpc_url: String,
+    seed: Vec<u8>,
+    amount: u64,
+) -> Result<()> {
+    let client = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
+    let program_id = get_program_id();
+
+    let authority = keypair.pubkey();
+    let (counter_pubkey, bump) = get_counter_address(&authority, &seed, &program_id);
+    let (config_pubkey, _) = get_config_address(&program_id);
+
+    println!("PDA bump: {}", bump);
+    println!("Authority: {}", authority);
+    println!("Counter address: {}", counter_pubkey);
+    println!("Decreasing by: {}", amount);
+
+    // Check if counter exists. A pre-funded address has no data yet.
+    let counter_key = client
+        .get_account(&counter_pubkey)
+        .ok()
+        .and_then(|account| account.data.first().copied())
+        .ok_or_else(|| {
+            anyhow!("Counter does not exist. Create it first with the 'create' command.")
+        })?;
+
+    // Prepare instruction data: discriminator (2) + amount (8 bytes)
+    let mut instruction_data = vec![2]; // Decrease discriminator
+    instruction_data.extend_from_slice(&amount.to_le_bytes());
+
+    let mut accounts = vec![
+        AccountMeta::new(counter_pubkey, false),
+        AccountMeta::new_readonly(authority, true),
+        AccountMeta::new_readonly(config_pubkey, false),
+    ];
+
+    // Record the change when the counter keeps a history
+    let (history_pubkey, _) = get_history_address(&counter_pubkey, &program_id);
+    if client.get_account(&history_pubkey).is_ok() {
+        accounts.push(AccountMeta::new(history_pubkey, false));
+    }
+
+    // Create instruction
+    let decrease_instruction = Instruction {
+        program_id,
+        accounts,
+        data: instruction_data,
+    };
+
+    // Get recent blockhash
+    let recent_blockhash = client.get_latest_blockhash()?;
+
+    // Create and send transaction
+    let transaction = Transaction::new_signed_with_payer(
+        &[decrease_instruction],
+        Some(&authority),
+        &[&keypair],
+        recent_blockhash,
+    );
+
+    let signature = client
+        .send_and_confirm_transaction(&transaction)
+        .map_err(decode_client_error)?;
+    println!("✅ Counter decreased successfully!");
+    println!("Transaction signature: {}", signature);
+
+    let output = fetch_transaction_output(&client, &signature)?;
+    if let Some(return_data) = output.return_data {
+        print_value_change(counter_key, &return_data)?;
+    }
+    print_events(&output.events);
+
+    Ok(())
+}
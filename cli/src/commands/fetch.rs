@@ -1,18 +1,20 @@
 use anyhow::{anyhow, Result};
-use counter_pinocchio::state::Counter;
+use counter_pinocchio::state::{
+    AnyCounter, Counter, CounterHeader, CounterV1, Limits, RateLimit, VersionedCounter,
+};
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::{
     commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Keypair, signer::Signer,
 };
 
-use crate::helpers::counter::{get_counter_address, get_program_id};
+use crate::helpers::counter::{format_limit, get_counter_address, get_program_id};
 
-pub async fn fetch_counter(keypair: Keypair, rpc_url: String) -> Result<()> {
+pub async fn fetch_counter(keypair: Keypair, rpc_url: String, seed: Vec<u8>) -> Result<()> {
     let client = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
     let program_id = get_program_id();
 
     let authority = keypair.pubkey();
-    let (counter_pubkey, _bump) = get_counter_address(&authority, &program_id);
+    let (counter_pubkey, _bump) = get_counter_address(&authority, &seed, &program_id);
 
     println!("Authority: {}", authority);
     println!("Counter address: {}", counter_pubkey);
@@ -22,16 +24,107 @@ pub async fn fetch_counter(keypair: Keypair, rpc_url: String) -> Result<()> {
         anyhow!("Counter does not exist. Create it first with the 'create' command.")
     })?;
 
-    // Deserialize counter data using the program's load method
-    let counter = Counter::load(&account.data)
-        .map_err(|e| anyhow!("Failed to deserialize counter data: {:?}", e))?;
+    // Signed and wide counters have a single version and no limits
+    match AnyCounter::load(&account.data) {
+        Ok(AnyCounter::Signed(counter)) => {
+            println!("📊 Counter Information:");
+            println!("  Kind: signed");
+            print_header(counter);
+            println!("  Value: {}", counter.value());
+            print_metadata(counter);
+            return Ok(());
+        }
+        Ok(AnyCounter::Wide(counter)) => {
+            println!("📊 Counter Information:");
+            println!("  Kind: wide");
+            print_header(counter);
+            println!("  Value: {}", counter.value());
+            print_metadata(counter);
+            return Ok(());
+        }
+        _ => {}
+    }
 
-    let authority_pubkey = Pubkey::new_from_array(counter.authority);
+    // Deserialize counter data with the loader of its version
+    let counter = match VersionedCounter::load(&account.data)
+        .map_err(|e| anyhow!("Failed to deserialize counter data: {:?}", e))?
+    {
+        VersionedCounter::V1(counter) => {
+            println!("📊 Counter Information:");
+            println!("  Kind: unsigned");
+            println!("  Version: {}", CounterV1::VERSION);
+            println!("  Authority: {}", Pubkey::new_from_array(counter.authority));
+            println!("  Value: {}", counter.value());
+            println!("  Bump: {}", counter.bump);
+            println!();
+            println!(
+                "Upgrade it to version {} with the 'migrate' command.",
+                Counter::VERSION
+            );
+            return Ok(());
+        }
+        VersionedCounter::V2(counter) => counter,
+    };
 
     println!("📊 Counter Information:");
-    println!("  Authority: {}", authority_pubkey);
-    println!("  Value: {}", counter.value);
-    println!("  Bump: {}", counter.bump);
+    println!("  Kind: unsigned");
+    print_header(counter);
+    println!("  Value: {}", counter.value());
+    let limits = counter.limits();
+    println!("  Min: {}", format_limit(limits.min, Limits::UNBOUNDED.min));
+    println!("  Max: {}", format_limit(limits.max, Limits::UNBOUNDED.max));
+    println!(
+        "  Max step: {}",
+        format_limit(limits.max_step, Limits::UNBOUNDED.max_step)
+    );
+    println!("  Delegates: {}", counter.delegate_count());
+    print_metadata(counter);
 
     Ok(())
 }
+
+/// Prints the version and authorities every counter kind has.
+fn print_header(header: &CounterHeader) {
+    let authority_pubkey = Pubkey::new_from_array(header.authority);
+    let pending_authority_pubkey = Pubkey::new_from_array(header.pending_authority);
+
+    println!("  Version: {}", header.version);
+    println!("  Authority: {}", authority_pubkey);
+    if pending_authority_pubkey != Pubkey::default() {
+        println!("  Pending authority: {}", pending_authority_pubkey);
+    }
+}
+
+/// Prints the status, rate limit and update metadata every counter kind has.
+fn print_metadata(header: &CounterHeader) {
+    println!(
+        "  Status: {}",
+        if header.is_frozen() {
+            "frozen"
+        } else {
+            "active"
+        }
+    );
+    let rate_limit = header.rate_limit();
+    if rate_limit != RateLimit::DISABLED {
+        println!(
+            "  Rate limit: cooldown {} slots / {} seconds, at most {} per {} slots",
+            rate_limit.cooldown_slots,
+            rate_limit.cooldown_seconds,
+            rate_limit.window_max,
+            rate_limit.window_slots
+        );
+        println!(
+            "  Current window: {} added since slot {}",
+            header.window_total(),
+            header.window_start()
+        );
+    }
+    println!("  Update count: {}", header.update_count());
+    println!("  Last updated slot: {}", header.last_updated_slot());
+    println!(
+        "  Last updated unix timestamp: {}",
+        header.last_updated_unix_timestamp()
+    );
+    println!("  Bump: {}", header.bump);
+}
@@ -0,0 +1,107 @@
+use anyhow::{anyhow, Result};
+use counter_pinocchio::state::History;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::{AccountMeta, Instruction},
+    signature::Keypair,
+    signer::Signer,
+    transaction::Transaction,
+};
+
+use crate::helpers::{
+    counter::{get_config_address, get_counter_address, get_history_address, get_program_id},
+    error::decode_client_error,
+};
+
+pub async fn initialize_history(keypair: Keypair, rpc_url: String, seed: Vec<u8>) -> Result<()> {
+    let client = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
+    let program_id = get_program_id();
+
+    let authority = keypair.pubkey();
+    let (counter_pubkey, _) = get_counter_address(&authority, &seed, &program_id);
+    let (config_pubkey, _) = get_config_address(&program_id);
+    let (history_pubkey, _) = get_history_address(&counter_pubkey, &program_id);
+
+    println!("Authority: {}", authority);
+    println!("Counter address: {}", counter_pubkey);
+    println!("History address: {}", history_pubkey);
+
+    // Check if counter exists
+    client.get_account(&counter_pubkey).map_err(|_| {
+        anyhow!("Counter does not exist. Create it first with the 'create' command.")
+    })?;
+
+    // Create instruction
+    let initialize_instruction = Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(counter_pubkey, false),
+            AccountMeta::new(authority, true),
+            AccountMeta::new_readonly(config_pubkey, false),
+            AccountMeta::new(history_pubkey, false),
+            // The authority pays for the history
+            AccountMeta::new(authority, true),
+            AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
+        ],
+        data: vec![26], // InitializeHistory discriminator
+    };
+
+    // Get recent blockhash
+    let recent_blockhash = client.get_latest_blockhash()?;
+
+    // Create and send transaction
+    let transaction = Transaction::new_signed_with_payer(
+        &[initialize_instruction],
+        Some(&authority),
+        &[&keypair],
+        recent_blockhash,
+    );
+
+    let signature = client
+        .send_and_confirm_transaction(&transaction)
+        .map_err(decode_client_error)?;
+    println!("✅ History initialized successfully!");
+    println!("Transaction signature: {}", signature);
+    println!(
+        "The last {} changes of the counter are recorded from now on.",
+        History::CAPACITY
+    );
+
+    Ok(())
+}
+
+pub async fn fetch_history(keypair: Keypair, rpc_url: String, seed: Vec<u8>) -> Result<()> {
+    let client = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
+    let program_id = get_program_id();
+
+    let authority = keypair.pubkey();
+    let (counter_pubkey, _) = get_counter_address(&authority, &seed, &program_id);
+    let (history_pubkey, _) = get_history_address(&counter_pubkey, &program_id);
+
+    println!("Authority: {}", authority);
+    println!("Counter address: {}", counter_pubkey);
+    println!("History address: {}", history_pubkey);
+
+    let account = client.get_account(&history_pubkey).map_err(|_| {
+        anyhow!("History does not exist. Create it first with the 'init-history' command.")
+    })?;
+    let history = History::load(&account.data)
+        .map_err(|e| anyhow!("Failed to deserialize history data: {:?}", e))?;
+
+    println!(
+        "📜 Counter History ({} of the last {} changes):",
+        history.len(),
+        History::CAPACITY
+    );
+    for entry in history.entries() {
+        println!(
+            "  Slot {}: {:+} -> {}",
+            entry.slot(),
+            entry.delta(),
+            entry.new_value()
+        );
+    }
+
+    Ok(())
+}
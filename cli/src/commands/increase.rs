@@ -1,60 +1,136 @@
 use anyhow::{anyhow, Result};
+use counter_pinocchio::state::StateKey;
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::{
     commitment_config::CommitmentConfig,
     instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
     signature::Keypair,
     signer::Signer,
     transaction::Transaction,
 };
 
-use crate::helpers::counter::{get_counter_address, get_program_id};
+use crate::helpers::{
+    counter::{
+        create_instruction_data, get_config_address, get_counter_address, get_delegate_address,
+        get_history_address, get_program_id,
+    },
+    error::decode_client_error,
+    transaction::{fetch_transaction_output, print_events, print_value_change},
+};
 
-pub async fn increase_counter(keypair: Keypair, rpc_url: String, amount: u64) -> Result<()> {
+pub async fn increase_counter(
+    keypair: Keypair,
+    rpc_url: String,
+    seed: Vec<u8>,
+    amount: u64,
+    create_if_missing: bool,
+    counter_authority: Option<Pubkey>,
+) -> Result<()> {
     let client = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
     let program_id = get_program_id();
 
-    let authority = keypair.pubkey();
-    let (counter_pubkey, bump) = get_counter_address(&authority, &program_id);
+    // With another counter authority the keypair signs as one of its delegates
+    let signer = keypair.pubkey();
+    let authority = counter_authority.unwrap_or(signer);
+    let (counter_pubkey, bump) = get_counter_address(&authority, &seed, &program_id);
+    let (config_pubkey, _) = get_config_address(&program_id);
+
+    if create_if_missing && authority != signer {
+        return Err(anyhow!(
+            "--create-if-missing cannot be used when increasing as a delegate"
+        ));
+    }
 
     println!("PDA bump: {}", bump);
     println!("Authority: {}", authority);
     println!("Counter address: {}", counter_pubkey);
+    if authority != signer {
+        println!("Delegate: {}", signer);
+    }
     println!("Increasing by: {}", amount);
 
-    // Check if counter exists
-    client.get_account(&counter_pubkey).map_err(|_| {
-        anyhow!("Counter does not exist. Create it first with the 'create' command.")
-    })?;
+    // The kind of counter determines how the returned values are decoded. A
+    // pre-funded address has no data yet, like a missing one.
+    let counter_key = match client
+        .get_account(&counter_pubkey)
+        .ok()
+        .and_then(|account| account.data.first().copied())
+    {
+        Some(key) => key,
+        None if create_if_missing => StateKey::Counter as u8,
+        None => {
+            return Err(anyhow!("Counter does not exist. Create it first with the 'create' command or pass --create-if-missing."));
+        }
+    };
+
+    let mut instructions = Vec::with_capacity(2);
+
+    if create_if_missing {
+        // Prepare instruction data: discriminator (8) + counter seed
+        let instruction_data = create_instruction_data(8, &seed, None); // CreateIdempotent discriminator
+
+        instructions.push(Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(counter_pubkey, false),
+                AccountMeta::new(authority, true),
+                AccountMeta::new_readonly(config_pubkey, false),
+                AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
+            ],
+            data: instruction_data,
+        });
+    }
 
     // Prepare instruction data: discriminator (1) + amount (8 bytes)
     let mut instruction_data = vec![1]; // Increase discriminator
     instruction_data.extend_from_slice(&amount.to_le_bytes());
 
+    let mut accounts = vec![
+        AccountMeta::new(counter_pubkey, false),
+        AccountMeta::new_readonly(signer, true),
+        AccountMeta::new_readonly(config_pubkey, false),
+    ];
+    if authority != signer {
+        let (delegate_pubkey, _) = get_delegate_address(&counter_pubkey, &signer, &program_id);
+        accounts.push(AccountMeta::new(delegate_pubkey, false));
+    }
+
+    // Record the change when the counter keeps a history
+    let (history_pubkey, _) = get_history_address(&counter_pubkey, &program_id);
+    if client.get_account(&history_pubkey).is_ok() {
+        accounts.push(AccountMeta::new(history_pubkey, false));
+    }
+
     // Create instruction
-    let increase_instruction = Instruction {
+    instructions.push(Instruction {
         program_id,
-        accounts: vec![
-            AccountMeta::new(counter_pubkey, false),
-            AccountMeta::new_readonly(authority, false),
-        ],
+        accounts,
         data: instruction_data,
-    };
+    });
 
     // Get recent blockhash
     let recent_blockhash = client.get_latest_blockhash()?;
 
     // Create and send transaction
     let transaction = Transaction::new_signed_with_payer(
-        &[increase_instruction],
-        Some(&authority),
+        &instructions,
+        Some(&signer),
         &[&keypair],
         recent_blockhash,
     );
 
-    let signature = client.send_and_confirm_transaction(&transaction)?;
+    let signature = client
+        .send_and_confirm_transaction(&transaction)
+        .map_err(decode_client_error)?;
     println!("✅ Counter increased successfully!");
     println!("Transaction signature: {}", signature);
 
+    let output = fetch_transaction_output(&client, &signature)?;
+    if let Some(return_data) = output.return_data {
+        print_value_change(counter_key, &return_data)?;
+    }
+    print_events(&output.events);
+
     Ok(())
 }
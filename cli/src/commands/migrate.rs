@@ -0,0 +1,82 @@
+use anyhow::{anyhow, Result};
+use counter_pinocchio::state::{Counter, VersionedCounter};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    transaction::Transaction,
+};
+
+use crate::helpers::{
+    counter::{get_config_address, get_counter_address, get_program_id},
+    error::decode_client_error,
+    transaction::{fetch_transaction_output, print_events},
+};
+
+pub async fn migrate_counter(
+    keypair: Keypair,
+    rpc_url: String,
+    authority: Option<Pubkey>,
+) -> Result<()> {
+    let client = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
+    let program_id = get_program_id();
+
+    // Version 1 counters were only ever derived without a seed. The keypair
+    // pays for the migration, which anyone may do.
+    let payer = keypair.pubkey();
+    let authority = authority.unwrap_or(payer);
+    let (counter_pubkey, _) = get_counter_address(&authority, &[], &program_id);
+    let (config_pubkey, _) = get_config_address(&program_id);
+
+    println!("Authority: {}", authority);
+    println!("Counter address: {}", counter_pubkey);
+
+    // Check if counter exists and still uses an older layout
+    let account = client.get_account(&counter_pubkey).map_err(|_| {
+        anyhow!("Counter does not exist. Create it first with the 'create' command.")
+    })?;
+    let version = VersionedCounter::load(&account.data)
+        .map_err(|e| anyhow!("Failed to deserialize counter data: {:?}", e))?
+        .version();
+    if version == Counter::VERSION {
+        return Err(anyhow!("Counter already uses version {}", Counter::VERSION));
+    }
+    println!("Migrating from version {} to {}", version, Counter::VERSION);
+
+    // Create instruction
+    let migrate_instruction = Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(counter_pubkey, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(config_pubkey, false),
+            AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
+        ],
+        data: vec![16], // Migrate discriminator
+    };
+
+    // Get recent blockhash
+    let recent_blockhash = client.get_latest_blockhash()?;
+
+    // Create and send transaction
+    let transaction = Transaction::new_signed_with_payer(
+        &[migrate_instruction],
+        Some(&payer),
+        &[&keypair],
+        recent_blockhash,
+    );
+
+    let signature = client
+        .send_and_confirm_transaction(&transaction)
+        .map_err(decode_client_error)?;
+    println!("✅ Counter migrated successfully!");
+    println!("Transaction signature: {}", signature);
+
+    let output = fetch_transaction_output(&client, &signature)?;
+    print_events(&output.events);
+
+    Ok(())
+}
@@ -1,3 +1,13 @@
+pub mod approve_delegate;
+pub mod close;
+pub mod config;
+pub mod configure;
 pub mod create;
+pub mod create_multisig;
+pub mod decrease;
 pub mod fetch;
+pub mod history;
 pub mod increase;
+pub mod migrate;
+pub mod rate_limit;
+pub mod revoke_delegate;
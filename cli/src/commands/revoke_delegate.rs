@@ -0,0 +1,77 @@
+use anyhow::{anyhow, Result};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    transaction::Transaction,
+};
+
+use crate::helpers::{
+    counter::{get_config_address, get_counter_address, get_delegate_address, get_program_id},
+    error::decode_client_error,
+    transaction::{fetch_transaction_output, print_events},
+};
+
+pub async fn revoke_delegate(
+    keypair: Keypair,
+    rpc_url: String,
+    seed: Vec<u8>,
+    delegate: Pubkey,
+) -> Result<()> {
+    let client = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
+    let program_id = get_program_id();
+
+    let authority = keypair.pubkey();
+    let (counter_pubkey, _) = get_counter_address(&authority, &seed, &program_id);
+    let (config_pubkey, _) = get_config_address(&program_id);
+    let (delegate_pubkey, _) = get_delegate_address(&counter_pubkey, &delegate, &program_id);
+
+    println!("Authority: {}", authority);
+    println!("Counter address: {}", counter_pubkey);
+    println!("Delegate: {}", delegate);
+    println!("Delegate account: {}", delegate_pubkey);
+
+    // Check if the delegate exists
+    client.get_account(&delegate_pubkey).map_err(|_| {
+        anyhow!("Delegate does not exist. Approve it first with the 'approve-delegate' command.")
+    })?;
+
+    // Create instruction
+    let revoke_instruction = Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(counter_pubkey, false),
+            AccountMeta::new(authority, true),
+            AccountMeta::new_readonly(config_pubkey, false),
+            AccountMeta::new(delegate_pubkey, false),
+            // The delegate account's rent goes back to the authority
+            AccountMeta::new(authority, false),
+        ],
+        data: vec![14], // RevokeDelegate discriminator
+    };
+
+    // Get recent blockhash
+    let recent_blockhash = client.get_latest_blockhash()?;
+
+    // Create and send transaction
+    let transaction = Transaction::new_signed_with_payer(
+        &[revoke_instruction],
+        Some(&authority),
+        &[&keypair],
+        recent_blockhash,
+    );
+
+    let signature = client
+        .send_and_confirm_transaction(&transaction)
+        .map_err(decode_client_error)?;
+    println!("✅ Delegate revoked successfully!");
+    println!("Transaction signature: {}", signature);
+
+    let output = fetch_transaction_output(&client, &signature)?;
+    print_events(&output.events);
+
+    Ok(())
+}
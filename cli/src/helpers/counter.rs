@@ -1,10 +1,72 @@
-use counter_pinocchio::ID as PROGRAM_ID;
-use solana_sdk::pubkey::Pubkey;
+use anyhow::{anyhow, Result};
+use counter_pinocchio::{state::Limits, ID as PROGRAM_ID};
+use solana_sdk::pubkey::{Pubkey, MAX_SEED_LEN};
 
 pub fn get_program_id() -> Pubkey {
     Pubkey::new_from_array(PROGRAM_ID)
 }
 
-pub fn get_counter_address(authority: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
-    Pubkey::find_program_address(&[b"counter", authority.as_ref()], program_id)
+/// Encodes the `--name`/`--index` selection into the counter's PDA seed.
+/// Indexes are stored as little-endian `u64` bytes.
+pub fn counter_seed(name: Option<&str>, index: Option<u64>) -> Result<Vec<u8>> {
+    match (name, index) {
+        (Some(name), _) if name.len() > MAX_SEED_LEN => Err(anyhow!(
+            "Counter name must be at most {} bytes",
+            MAX_SEED_LEN
+        )),
+        (Some(name), _) => Ok(name.as_bytes().to_vec()),
+        (None, Some(index)) => Ok(index.to_le_bytes().to_vec()),
+        (None, None) => Ok(Vec::new()),
+    }
+}
+
+pub fn get_counter_address(authority: &Pubkey, seed: &[u8], program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"counter", authority.as_ref(), seed], program_id)
+}
+
+pub fn get_config_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"config"], program_id)
+}
+
+pub fn get_multisig_address(creator: &Pubkey, seed: &[u8], program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"multisig", creator.as_ref(), seed], program_id)
+}
+
+pub fn get_delegate_address(
+    counter: &Pubkey,
+    delegate: &Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"delegate", counter.as_ref(), delegate.as_ref()],
+        program_id,
+    )
+}
+
+pub fn get_history_address(counter: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"history", counter.as_ref()], program_id)
+}
+
+/// Encodes `Create`/`CreateIdempotent` instruction data: empty for the
+/// default counter without limits, otherwise the length-prefixed seed
+/// followed by the optional limits.
+pub fn create_instruction_data(discriminator: u8, seed: &[u8], limits: Option<&Limits>) -> Vec<u8> {
+    let mut data = vec![discriminator];
+    if seed.is_empty() && limits.is_none() {
+        return data;
+    }
+    data.push(seed.len() as u8);
+    data.extend_from_slice(seed);
+    if let Some(limits) = limits {
+        data.extend_from_slice(&limits.to_bytes());
+    }
+    data
+}
+
+/// Renders a limit, showing the unbounded sentinel as `none`.
+pub fn format_limit(limit: u64, unbounded: u64) -> String {
+    match limit == unbounded {
+        true => "none".to_string(),
+        false => limit.to_string(),
+    }
 }
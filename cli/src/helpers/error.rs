@@ -0,0 +1,24 @@
+use anyhow::anyhow;
+use counter_pinocchio::error::CounterError;
+use pinocchio::program_error::ToStr;
+use solana_client::client_error::ClientError;
+use solana_sdk::{instruction::InstructionError, transaction::TransactionError};
+
+/// Converts a failed RPC call into a readable error, decoding the counter
+/// program's custom error codes when an instruction failed with one.
+pub fn decode_client_error(error: ClientError) -> anyhow::Error {
+    if let Some(TransactionError::InstructionError(index, InstructionError::Custom(code))) =
+        error.get_transaction_error()
+    {
+        if let Ok(counter_error) = CounterError::try_from(code) {
+            return anyhow!(
+                "{} (code {}) in instruction {}",
+                counter_error.to_str::<CounterError>(),
+                code,
+                index
+            );
+        }
+    }
+
+    error.into()
+}
@@ -1,2 +1,4 @@
 pub mod counter;
+pub mod error;
 pub mod keypair;
+pub mod transaction;
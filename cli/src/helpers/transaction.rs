@@ -0,0 +1,105 @@
+use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use counter_pinocchio::{
+    events::CounterEvent,
+    return_data::{SignedValueChange, ValueChange, WideValueChange},
+    state::StateKey,
+};
+use solana_client::{rpc_client::RpcClient, rpc_config::RpcTransactionConfig};
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature};
+
+/// What the counter program reported while executing a transaction.
+pub struct TransactionOutput {
+    /// Raw return data, decoded by `print_value_change`.
+    pub return_data: Option<Vec<u8>>,
+    pub events: Vec<CounterEvent>,
+}
+
+/// Reads the return data and events of a confirmed transaction from its
+/// metadata.
+pub fn fetch_transaction_output(
+    client: &RpcClient,
+    signature: &Signature,
+) -> Result<TransactionOutput> {
+    let transaction = client.get_transaction_with_config(
+        signature,
+        RpcTransactionConfig {
+            commitment: Some(CommitmentConfig::confirmed()),
+            max_supported_transaction_version: Some(0),
+            ..Default::default()
+        },
+    )?;
+
+    let meta = transaction
+        .transaction
+        .meta
+        .ok_or_else(|| anyhow!("Transaction has no metadata"))?;
+
+    let return_data = meta
+        .return_data
+        .map(|return_data| STANDARD.decode(return_data.data.0))
+        .transpose()?;
+
+    let events = meta
+        .log_messages
+        .map(|logs| decode_events(&logs))
+        .unwrap_or_default();
+
+    Ok(TransactionOutput {
+        return_data,
+        events,
+    })
+}
+
+/// Prints the value change returned for a counter whose `StateKey` is
+/// `key`, which determines the width of the values.
+pub fn print_value_change(key: u8, return_data: &[u8]) -> Result<()> {
+    let unexpected = || anyhow!("Unexpected return data: {:?}", return_data);
+    let (previous, new) = match key {
+        key if key == StateKey::SignedCounter as u8 => SignedValueChange::from_bytes(return_data)
+            .map(|change| (change.previous.to_string(), change.new.to_string()))
+            .ok_or_else(unexpected)?,
+        key if key == StateKey::WideCounter as u8 => WideValueChange::from_bytes(return_data)
+            .map(|change| (change.previous.to_string(), change.new.to_string()))
+            .ok_or_else(unexpected)?,
+        _ => ValueChange::from_bytes(return_data)
+            .map(|change| (change.previous.to_string(), change.new.to_string()))
+            .ok_or_else(unexpected)?,
+    };
+    println!("Previous value: {}", previous);
+    println!("New value: {}", new);
+    Ok(())
+}
+
+/// Decodes every `CounterEvent` found in `Program data:` log lines.
+pub fn decode_events(logs: &[String]) -> Vec<CounterEvent> {
+    logs.iter()
+        .filter_map(|log| log.strip_prefix("Program data: "))
+        .filter_map(|data| STANDARD.decode(data).ok())
+        .filter_map(|bytes| CounterEvent::from_bytes(&bytes))
+        .collect()
+}
+
+/// Formats a value logged in a `CounterEvent` about an account whose
+/// `StateKey` is `counter_kind`.
+fn format_event_value(counter_kind: u8, value: u128) -> String {
+    if counter_kind == StateKey::SignedCounter as u8 {
+        (value as i128).to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+pub fn print_events(events: &[CounterEvent]) {
+    for event in events {
+        println!(
+            "📣 {:?} event at slot {}: {} -> {} (counter {}, authority {})",
+            event.kind,
+            event.slot,
+            format_event_value(event.counter_kind, event.old_value),
+            format_event_value(event.counter_kind, event.new_value),
+            Pubkey::new_from_array(event.counter),
+            Pubkey::new_from_array(event.authority),
+        );
+    }
+}
@@ -1,11 +1,27 @@
 use anyhow::Result;
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
+use counter_pinocchio::state::{Limits, RateLimit};
+use solana_sdk::pubkey::Pubkey;
 
 mod commands;
 mod helpers;
 
-use commands::{create::create_counter, fetch::fetch_counter, increase::increase_counter};
-use helpers::keypair::load_keypair;
+use commands::{
+    approve_delegate::approve_delegate,
+    close::close_counter,
+    config::{initialize_config, update_config},
+    configure::configure_counter,
+    create::{create_counter, CounterKind},
+    create_multisig::create_multisig,
+    decrease::decrease_counter,
+    fetch::fetch_counter,
+    history::{fetch_history, initialize_history},
+    increase::increase_counter,
+    migrate::migrate_counter,
+    rate_limit::configure_rate_limit,
+    revoke_delegate::revoke_delegate,
+};
+use helpers::{counter::counter_seed, keypair::load_keypair};
 
 #[derive(Parser)]
 #[command(name = "counter")]
@@ -15,6 +31,52 @@ struct Cli {
     command: Commands,
 }
 
+/// Selects one of the authority's counters. Without either flag the
+/// authority's default (unnamed) counter is used.
+#[derive(Args)]
+struct CounterSeedArgs {
+    /// Name of the counter (up to 32 bytes)
+    #[arg(short, long, conflicts_with = "index")]
+    name: Option<String>,
+    /// Numeric index of the counter
+    #[arg(short, long)]
+    index: Option<u64>,
+}
+
+impl CounterSeedArgs {
+    fn seed(&self) -> Result<Vec<u8>> {
+        counter_seed(self.name.as_deref(), self.index)
+    }
+}
+
+/// Optional business limits of a counter. Omitted limits are unbounded.
+#[derive(Args)]
+struct LimitArgs {
+    /// Lowest value the counter may hold
+    #[arg(long)]
+    min: Option<u64>,
+    /// Highest value the counter may hold
+    #[arg(long)]
+    max: Option<u64>,
+    /// Largest amount a single increase or decrease may apply
+    #[arg(long)]
+    max_step: Option<u64>,
+}
+
+impl LimitArgs {
+    fn is_empty(&self) -> bool {
+        self.min.is_none() && self.max.is_none() && self.max_step.is_none()
+    }
+
+    fn limits(&self) -> Limits {
+        Limits {
+            min: self.min.unwrap_or(Limits::UNBOUNDED.min),
+            max: self.max.unwrap_or(Limits::UNBOUNDED.max),
+            max_step: self.max_step.unwrap_or(Limits::UNBOUNDED.max_step),
+        }
+    }
+}
+
 #[derive(Subcommand)]
 enum Commands {
     /// Create a new counter account
@@ -25,6 +87,13 @@ enum Commands {
         /// RPC URL for the Solana cluster
         #[arg(short, long, default_value = "https://api.devnet.solana.com")]
         rpc_url: String,
+        #[command(flatten)]
+        counter: CounterSeedArgs,
+        /// Kind of value the counter holds
+        #[arg(long, value_enum, default_value_t = CounterKind::Unsigned)]
+        kind: CounterKind,
+        #[command(flatten)]
+        limits: LimitArgs,
     },
     /// Increase the counter value
     Increase {
@@ -34,9 +103,204 @@ enum Commands {
         /// RPC URL for the Solana cluster
         #[arg(short, long, default_value = "https://api.devnet.solana.com")]
         rpc_url: String,
+        #[command(flatten)]
+        counter: CounterSeedArgs,
         /// Amount to increase by (default: 1)
         #[arg(short = 'm', long, default_value = "1")]
         amount: u64,
+        /// Create the counter in the same transaction if it does not exist yet
+        #[arg(long)]
+        create_if_missing: bool,
+        /// Authority of the counter, when the keypair is one of its delegates
+        #[arg(long)]
+        authority: Option<Pubkey>,
+    },
+    /// Decrease the counter value
+    Decrease {
+        /// Path to the keypair file
+        #[arg(short, long)]
+        keypair: String,
+        /// RPC URL for the Solana cluster
+        #[arg(short, long, default_value = "https://api.devnet.solana.com")]
+        rpc_url: String,
+        #[command(flatten)]
+        counter: CounterSeedArgs,
+        /// Amount to decrease by (default: 1)
+        #[arg(short = 'm', long, default_value = "1")]
+        amount: u64,
+    },
+    /// Replace the counter's min, max and max step
+    Configure {
+        /// Path to the keypair file
+        #[arg(short, long)]
+        keypair: String,
+        /// RPC URL for the Solana cluster
+        #[arg(short, long, default_value = "https://api.devnet.solana.com")]
+        rpc_url: String,
+        #[command(flatten)]
+        counter: CounterSeedArgs,
+        #[command(flatten)]
+        limits: LimitArgs,
+    },
+    /// Let another key increase the counter up to an allowance
+    ApproveDelegate {
+        /// Path to the keypair file
+        #[arg(short, long)]
+        keypair: String,
+        /// RPC URL for the Solana cluster
+        #[arg(short, long, default_value = "https://api.devnet.solana.com")]
+        rpc_url: String,
+        #[command(flatten)]
+        counter: CounterSeedArgs,
+        /// Key allowed to increase the counter
+        #[arg(short, long)]
+        delegate: Pubkey,
+        /// Total amount the delegate may add
+        #[arg(short, long)]
+        allowance: u64,
+        /// Last slot in which the delegate may act (default: never expires)
+        #[arg(short, long)]
+        expiry_slot: Option<u64>,
+    },
+    /// Remove a delegate and reclaim its rent
+    RevokeDelegate {
+        /// Path to the keypair file
+        #[arg(short, long)]
+        keypair: String,
+        /// RPC URL for the Solana cluster
+        #[arg(short, long, default_value = "https://api.devnet.solana.com")]
+        rpc_url: String,
+        #[command(flatten)]
+        counter: CounterSeedArgs,
+        /// Key whose delegation is removed
+        #[arg(short, long)]
+        delegate: Pubkey,
+    },
+    /// Replace the counter's increase rate limit. Omitted values disable
+    /// that part of the policy
+    RateLimit {
+        /// Path to the keypair file
+        #[arg(short, long)]
+        keypair: String,
+        /// RPC URL for the Solana cluster
+        #[arg(short, long, default_value = "https://api.devnet.solana.com")]
+        rpc_url: String,
+        #[command(flatten)]
+        counter: CounterSeedArgs,
+        /// Minimum number of slots between two updates
+        #[arg(long, default_value = "0")]
+        cooldown_slots: u64,
+        /// Minimum number of seconds between two updates
+        #[arg(long, default_value = "0")]
+        cooldown_seconds: u64,
+        /// Length in slots of the window limited by --window-max
+        #[arg(long, default_value = "0", requires = "window_max")]
+        window_slots: u64,
+        /// Maximum total increase per window
+        #[arg(long, default_value = "0", requires = "window_slots")]
+        window_max: u64,
+    },
+    /// Upgrade a counter of an older version to the current layout
+    Migrate {
+        /// Path to the keypair file, which pays for the larger account
+        #[arg(short, long)]
+        keypair: String,
+        /// RPC URL for the Solana cluster
+        #[arg(short, long, default_value = "https://api.devnet.solana.com")]
+        rpc_url: String,
+        /// Authority of the counter (default: the keypair)
+        #[arg(long)]
+        authority: Option<Pubkey>,
+    },
+    /// Create the program config. Must be signed by the program's upgrade authority
+    InitConfig {
+        /// Path to the upgrade authority keypair file
+        #[arg(short, long)]
+        keypair: String,
+        /// RPC URL for the Solana cluster
+        #[arg(short, long, default_value = "https://api.devnet.solana.com")]
+        rpc_url: String,
+        /// Key allowed to update the config (default: the keypair)
+        #[arg(long)]
+        admin: Option<Pubkey>,
+        /// Maximum step of counters created without limits (default: none)
+        #[arg(long, default_value_t = u64::MAX)]
+        default_max_step: u64,
+    },
+    /// Update the program config. Omitted values are kept
+    UpdateConfig {
+        /// Path to the admin keypair file
+        #[arg(short, long)]
+        keypair: String,
+        /// RPC URL for the Solana cluster
+        #[arg(short, long, default_value = "https://api.devnet.solana.com")]
+        rpc_url: String,
+        /// Hand the config over to another admin
+        #[arg(long)]
+        admin: Option<Pubkey>,
+        /// Reject every counter mutation until unpaused
+        #[arg(long, conflicts_with = "unpause")]
+        pause: bool,
+        /// Allow counter mutations again
+        #[arg(long)]
+        unpause: bool,
+        /// Maximum step of counters created without limits
+        #[arg(long)]
+        default_max_step: Option<u64>,
+    },
+    /// Create an m-of-n signer set that can be proposed as a counter authority
+    CreateMultisig {
+        /// Path to the keypair file
+        #[arg(short, long)]
+        keypair: String,
+        /// RPC URL for the Solana cluster
+        #[arg(short, long, default_value = "https://api.devnet.solana.com")]
+        rpc_url: String,
+        /// Name of the multisig (up to 32 bytes)
+        #[arg(short, long, default_value = "")]
+        name: String,
+        /// Number of members that must sign
+        #[arg(short = 'm', long)]
+        threshold: u8,
+        /// Members of the set
+        #[arg(short, long = "signer", required = true)]
+        signers: Vec<Pubkey>,
+    },
+    /// Close the counter account and reclaim its rent
+    Close {
+        /// Path to the keypair file
+        #[arg(short, long)]
+        keypair: String,
+        /// RPC URL for the Solana cluster
+        #[arg(short, long, default_value = "https://api.devnet.solana.com")]
+        rpc_url: String,
+        #[command(flatten)]
+        counter: CounterSeedArgs,
+        /// Account receiving the reclaimed lamports (default: the authority)
+        #[arg(short = 't', long)]
+        recipient: Option<Pubkey>,
+    },
+    /// Start recording the counter's changes in a history account
+    InitHistory {
+        /// Path to the keypair file
+        #[arg(short, long)]
+        keypair: String,
+        /// RPC URL for the Solana cluster
+        #[arg(short, long, default_value = "https://api.devnet.solana.com")]
+        rpc_url: String,
+        #[command(flatten)]
+        counter: CounterSeedArgs,
+    },
+    /// Print the counter's recent changes, oldest first
+    History {
+        /// Path to the keypair file (needed to derive counter address)
+        #[arg(short, long)]
+        keypair: String,
+        /// RPC URL for the Solana cluster
+        #[arg(short, long, default_value = "https://api.devnet.solana.com")]
+        rpc_url: String,
+        #[command(flatten)]
+        counter: CounterSeedArgs,
     },
     /// Fetch the current counter value
     Fetch {
@@ -46,6 +310,8 @@ enum Commands {
         /// RPC URL for the Solana cluster
         #[arg(short, long, default_value = "https://api.devnet.solana.com")]
         rpc_url: String,
+        #[command(flatten)]
+        counter: CounterSeedArgs,
     },
 }
 
@@ -54,33 +320,227 @@ async fn main() -> Result<()> {
     let cli = Cli::parse();
 
     match cli.command {
-        Commands::Create { keypair, rpc_url } => {
+        Commands::Create {
+            keypair,
+            rpc_url,
+            counter,
+            kind,
+            limits,
+        } => {
             println!("Loading keypair from: {}", keypair);
             println!("Using RPC URL: {}", rpc_url);
             println!();
 
             let kp = load_keypair(&keypair)?;
-            create_counter(kp, rpc_url).await?;
+            let seed = counter.seed()?;
+            let limits = (!limits.is_empty()).then(|| limits.limits());
+            create_counter(kp, rpc_url, seed, kind, limits).await?;
         }
         Commands::Increase {
             keypair,
             rpc_url,
+            counter,
             amount,
+            create_if_missing,
+            authority,
         } => {
             println!("Loading keypair from: {}", keypair);
             println!("Using RPC URL: {}", rpc_url);
             println!();
 
             let kp = load_keypair(&keypair)?;
-            increase_counter(kp, rpc_url, amount).await?;
+            let seed = counter.seed()?;
+            increase_counter(kp, rpc_url, seed, amount, create_if_missing, authority).await?;
         }
-        Commands::Fetch { keypair, rpc_url } => {
+        Commands::Decrease {
+            keypair,
+            rpc_url,
+            counter,
+            amount,
+        } => {
+            println!("Loading keypair from: {}", keypair);
+            println!("Using RPC URL: {}", rpc_url);
+            println!();
+
+            let kp = load_keypair(&keypair)?;
+            let seed = counter.seed()?;
+            decrease_counter(kp, rpc_url, seed, amount).await?;
+        }
+        Commands::Configure {
+            keypair,
+            rpc_url,
+            counter,
+            limits,
+        } => {
+            println!("Loading keypair from: {}", keypair);
+            println!("Using RPC URL: {}", rpc_url);
+            println!();
+
+            let kp = load_keypair(&keypair)?;
+            let seed = counter.seed()?;
+            configure_counter(kp, rpc_url, seed, limits.limits()).await?;
+        }
+        Commands::ApproveDelegate {
+            keypair,
+            rpc_url,
+            counter,
+            delegate,
+            allowance,
+            expiry_slot,
+        } => {
+            println!("Loading keypair from: {}", keypair);
+            println!("Using RPC URL: {}", rpc_url);
+            println!();
+
+            let kp = load_keypair(&keypair)?;
+            let seed = counter.seed()?;
+            approve_delegate(kp, rpc_url, seed, delegate, allowance, expiry_slot).await?;
+        }
+        Commands::RevokeDelegate {
+            keypair,
+            rpc_url,
+            counter,
+            delegate,
+        } => {
+            println!("Loading keypair from: {}", keypair);
+            println!("Using RPC URL: {}", rpc_url);
+            println!();
+
+            let kp = load_keypair(&keypair)?;
+            let seed = counter.seed()?;
+            revoke_delegate(kp, rpc_url, seed, delegate).await?;
+        }
+        Commands::RateLimit {
+            keypair,
+            rpc_url,
+            counter,
+            cooldown_slots,
+            cooldown_seconds,
+            window_slots,
+            window_max,
+        } => {
+            println!("Loading keypair from: {}", keypair);
+            println!("Using RPC URL: {}", rpc_url);
+            println!();
+
+            let kp = load_keypair(&keypair)?;
+            let seed = counter.seed()?;
+            let rate_limit = RateLimit {
+                cooldown_slots,
+                cooldown_seconds,
+                window_slots,
+                window_max,
+            };
+            configure_rate_limit(kp, rpc_url, seed, rate_limit).await?;
+        }
+        Commands::Migrate {
+            keypair,
+            rpc_url,
+            authority,
+        } => {
+            println!("Loading keypair from: {}", keypair);
+            println!("Using RPC URL: {}", rpc_url);
+            println!();
+
+            let kp = load_keypair(&keypair)?;
+            migrate_counter(kp, rpc_url, authority).await?;
+        }
+        Commands::InitConfig {
+            keypair,
+            rpc_url,
+            admin,
+            default_max_step,
+        } => {
+            println!("Loading keypair from: {}", keypair);
+            println!("Using RPC URL: {}", rpc_url);
+            println!();
+
+            let kp = load_keypair(&keypair)?;
+            initialize_config(kp, rpc_url, admin, default_max_step).await?;
+        }
+        Commands::UpdateConfig {
+            keypair,
+            rpc_url,
+            admin,
+            pause,
+            unpause,
+            default_max_step,
+        } => {
+            println!("Loading keypair from: {}", keypair);
+            println!("Using RPC URL: {}", rpc_url);
+            println!();
+
+            let kp = load_keypair(&keypair)?;
+            let paused = (pause || unpause).then_some(pause);
+            update_config(kp, rpc_url, admin, paused, default_max_step).await?;
+        }
+        Commands::CreateMultisig {
+            keypair,
+            rpc_url,
+            name,
+            threshold,
+            signers,
+        } => {
+            println!("Loading keypair from: {}", keypair);
+            println!("Using RPC URL: {}", rpc_url);
+            println!();
+
+            let kp = load_keypair(&keypair)?;
+            let seed = counter_seed(Some(&name), None)?;
+            create_multisig(kp, rpc_url, seed, threshold, signers).await?;
+        }
+        Commands::Close {
+            keypair,
+            rpc_url,
+            counter,
+            recipient,
+        } => {
+            println!("Loading keypair from: {}", keypair);
+            println!("Using RPC URL: {}", rpc_url);
+            println!();
+
+            let kp = load_keypair(&keypair)?;
+            let seed = counter.seed()?;
+            close_counter(kp, rpc_url, seed, recipient).await?;
+        }
+        Commands::InitHistory {
+            keypair,
+            rpc_url,
+            counter,
+        } => {
+            println!("Loading keypair from: {}", keypair);
+            println!("Using RPC URL: {}", rpc_url);
+            println!();
+
+            let kp = load_keypair(&keypair)?;
+            let seed = counter.seed()?;
+            initialize_history(kp, rpc_url, seed).await?;
+        }
+        Commands::History {
+            keypair,
+            rpc_url,
+            counter,
+        } => {
+            println!("Loading keypair from: {}", keypair);
+            println!("Using RPC URL: {}", rpc_url);
+            println!();
+
+            let kp = load_keypair(&keypair)?;
+            let seed = counter.seed()?;
+            fetch_history(kp, rpc_url, seed).await?;
+        }
+        Commands::Fetch {
+            keypair,
+            rpc_url,
+            counter,
+        } => {
             println!("Loading keypair from: {}", keypair);
             println!("Using RPC URL: {}", rpc_url);
             println!();
 
             let kp = load_keypair(&keypair)?;
-            fetch_counter(kp, rpc_url).await?;
+            let seed = counter.seed()?;
+            fetch_counter(kp, rpc_url, seed).await?;
         }
     }
 
@@ -3,8 +3,8 @@ use solana_sdk::pubkey;
 use solana_sdk::pubkey::Pubkey;
 use {
     counter_pinocchio::{
-        instructions::{Create, Increase},
-        state::{to_bytes, Counter, StateKey},
+        instructions::{BatchIncrease, Create, Increase},
+        state::{Config, Counter},
         ID,
     },
     mollusk_svm::Mollusk,
@@ -12,7 +12,6 @@ use {
     solana_sdk::{
         account::Account,
         instruction::{AccountMeta, Instruction},
-        system_program,
     },
 };
 
@@ -20,8 +19,7 @@ pub const PROGRAM: Pubkey = Pubkey::new_from_array(ID);
 pub const AUTHORITY: Pubkey = pubkey!("Co11111111111111111111111111111111111111111");
 
 pub fn mollusk() -> Mollusk {
-    let mollusk = Mollusk::new(&PROGRAM, "../target/deploy/counter_pinocchio");
-    mollusk
+    Mollusk::new(&PROGRAM, "../target/deploy/counter_pinocchio")
 }
 
 /// Helper function to create instruction data for increase
@@ -33,7 +31,51 @@ fn create_increase_instruction_data(amount: u64) -> Vec<u8> {
 
 /// Helper function to derive counter PDA
 fn derive_counter_pda(authority: &Pubkey) -> (Pubkey, u8) {
-    Pubkey::find_program_address(&Counter::seeds(&authority.to_bytes()), &PROGRAM)
+    Pubkey::find_program_address(&Counter::seeds(&authority.to_bytes(), &[]), &PROGRAM)
+}
+
+/// Helper function to build a `BatchIncrease` of `count` indexed counters,
+/// each increased by 1, with the accounts it needs
+fn batch_increase_setup(
+    count: u64,
+    authority_account: &Account,
+    config: &(Pubkey, Account),
+) -> (Instruction, Vec<(Pubkey, Account)>) {
+    let mut data = vec![*BatchIncrease::DISCRIMINATOR];
+    let mut metas = Vec::new();
+    let mut accounts = Vec::new();
+
+    for index in 0..count {
+        let seed = index.to_le_bytes();
+        let (counter_pda, bump) =
+            Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &seed), &PROGRAM);
+        let mut counter_account = Account {
+            lamports: 1_000_000,
+            data: vec![0; Counter::LEN],
+            owner: PROGRAM,
+            executable: false,
+            rent_epoch: 0,
+        };
+        Counter::load_mut(&mut counter_account.data, false)
+            .unwrap()
+            .set_inner(AUTHORITY.to_bytes(), bump, &seed);
+
+        data.extend_from_slice(&1u64.to_le_bytes());
+        metas.push(AccountMeta::new(counter_pda, false));
+        accounts.push((counter_pda, counter_account));
+    }
+
+    metas.push(AccountMeta::new_readonly(AUTHORITY, true));
+    metas.push(AccountMeta::new_readonly(config.0, false));
+    accounts.push((AUTHORITY, authority_account.clone()));
+    accounts.push(config.clone());
+
+    let instruction = Instruction {
+        program_id: PROGRAM,
+        accounts: metas,
+        data,
+    };
+    (instruction, accounts)
 }
 
 fn main() {
@@ -41,13 +83,17 @@ fn main() {
 
     // Setup test accounts
     let (counter_pda, bump) = derive_counter_pda(&AUTHORITY);
-    let authority_account = Account::new(1_000_000_000, 0, &system_program::ID);
     let (system_program, system_account) = program::keyed_account_for_system_program();
+    let authority_account = Account::new(1_000_000_000, 0, &system_program);
+    // Uninitialized config: the program is not paused
+    let (config_pda, _) = Pubkey::find_program_address(&Config::seeds(), &PROGRAM);
+    let config_account = Account::default();
 
     // Prepare accounts for create instruction
     let create_accounts = vec![
-        (counter_pda, Account::new(0, 0, &system_program::ID)),
+        (counter_pda, Account::new(0, 0, &system_program)),
         (AUTHORITY, authority_account.clone()),
+        (config_pda, config_account.clone()),
         (system_program, system_account.clone()),
     ];
 
@@ -57,30 +103,28 @@ fn main() {
         accounts: vec![
             AccountMeta::new(counter_pda, false),
             AccountMeta::new(AUTHORITY, true),
-            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(config_pda, false),
+            AccountMeta::new_readonly(system_program, false),
         ],
         data: vec![*Create::DISCRIMINATOR],
     };
 
     // Prepare accounts for increase instructions
-    let counter = Counter {
-        authority: AUTHORITY.to_bytes(),
-        bump,
-        key: StateKey::Counter,
-        value: 0,
-    };
-
-    let counter_account = Account {
+    let mut counter_account = Account {
         lamports: 1_000_000,
-        data: unsafe { to_bytes(&counter, Counter::LEN).to_vec() },
+        data: vec![0; Counter::LEN],
         owner: PROGRAM,
         executable: false,
         rent_epoch: 0,
     };
+    Counter::load_mut(&mut counter_account.data, false)
+        .unwrap()
+        .set_inner(AUTHORITY.to_bytes(), bump, &[]);
 
     let increase_accounts = vec![
         (counter_pda, counter_account),
-        (AUTHORITY, authority_account),
+        (AUTHORITY, authority_account.clone()),
+        (config_pda, config_account.clone()),
     ];
 
     // Different increase instructions to benchmark
@@ -88,7 +132,8 @@ fn main() {
         program_id: PROGRAM,
         accounts: vec![
             AccountMeta::new(counter_pda, false),
-            AccountMeta::new_readonly(AUTHORITY, false),
+            AccountMeta::new_readonly(AUTHORITY, true),
+            AccountMeta::new_readonly(config_pda, false),
         ],
         data: create_increase_instruction_data(1),
     };
@@ -97,7 +142,8 @@ fn main() {
         program_id: PROGRAM,
         accounts: vec![
             AccountMeta::new(counter_pda, false),
-            AccountMeta::new_readonly(AUTHORITY, false),
+            AccountMeta::new_readonly(AUTHORITY, true),
+            AccountMeta::new_readonly(config_pda, false),
         ],
         data: create_increase_instruction_data(100),
     };
@@ -106,11 +152,22 @@ fn main() {
         program_id: PROGRAM,
         accounts: vec![
             AccountMeta::new(counter_pda, false),
-            AccountMeta::new_readonly(AUTHORITY, false),
+            AccountMeta::new_readonly(AUTHORITY, true),
+            AccountMeta::new_readonly(config_pda, false),
         ],
         data: create_increase_instruction_data(u64::MAX / 2),
     };
 
+    // Batches of increasing size, to read the per-counter cost off the
+    // difference between them
+    let config = (config_pda, config_account);
+    let (batch_1_instruction, batch_1_accounts) =
+        batch_increase_setup(1, &authority_account, &config);
+    let (batch_10_instruction, batch_10_accounts) =
+        batch_increase_setup(10, &authority_account, &config);
+    let (batch_20_instruction, batch_20_accounts) =
+        batch_increase_setup(20, &authority_account, &config);
+
     // Run benchmarks
     MolluskComputeUnitBencher::new(mollusk)
         .bench(("create_counter", &create_instruction, &create_accounts))
@@ -125,6 +182,21 @@ fn main() {
             &increase_max_instruction,
             &increase_accounts,
         ))
+        .bench((
+            "batch_increase_1_counter",
+            &batch_1_instruction,
+            &batch_1_accounts,
+        ))
+        .bench((
+            "batch_increase_10_counters",
+            &batch_10_instruction,
+            &batch_10_accounts,
+        ))
+        .bench((
+            "batch_increase_20_counters",
+            &batch_20_instruction,
+            &batch_20_accounts,
+        ))
         .must_pass(true)
         .out_dir("../benches")
         .execute();
@@ -3,7 +3,12 @@ use pinocchio::{
     ProgramResult,
 };
 
-use crate::instructions::{Create, Increase};
+use crate::instructions::{
+    AcceptAuthority, ApproveDelegate, BatchIncrease, Close, CompareAndSet, Configure,
+    ConfigureRateLimit, Create, CreateIdempotent, CreateSigned, CreateTally, CreateWide, Decrease,
+    Freeze, Increase, IncrementTally, InitializeConfig, InitializeHistory, InitializeMultisig,
+    Migrate, ProposeAuthority, Reset, ResetTally, RevokeDelegate, Set, Thaw, UpdateConfig,
+};
 
 entrypoint!(process_instruction);
 
@@ -13,10 +18,71 @@ fn process_instruction(
     instruction_data: &[u8],
 ) -> ProgramResult {
     match instruction_data.split_first() {
-        Some((Create::DISCRIMINATOR, _)) => Create::try_from(accounts)?.process(),
+        Some((Create::DISCRIMINATOR, instruction_data)) => {
+            Create::try_from((instruction_data, accounts))?.process()
+        }
         Some((Increase::DISCRIMINATOR, instruction_data)) => {
             Increase::try_from((instruction_data, accounts))?.process()
         }
+        Some((Decrease::DISCRIMINATOR, instruction_data)) => {
+            Decrease::try_from((instruction_data, accounts))?.process()
+        }
+        Some((Reset::DISCRIMINATOR, _)) => Reset::try_from(accounts)?.process(),
+        Some((Set::DISCRIMINATOR, instruction_data)) => {
+            Set::try_from((instruction_data, accounts))?.process()
+        }
+        Some((Close::DISCRIMINATOR, _)) => Close::try_from(accounts)?.process(),
+        Some((ProposeAuthority::DISCRIMINATOR, instruction_data)) => {
+            ProposeAuthority::try_from((instruction_data, accounts))?.process()
+        }
+        Some((AcceptAuthority::DISCRIMINATOR, _)) => AcceptAuthority::try_from(accounts)?.process(),
+        Some((CreateIdempotent::DISCRIMINATOR, instruction_data)) => {
+            CreateIdempotent::try_from((instruction_data, accounts))?.process()
+        }
+        Some((CompareAndSet::DISCRIMINATOR, instruction_data)) => {
+            CompareAndSet::try_from((instruction_data, accounts))?.process()
+        }
+        Some((Configure::DISCRIMINATOR, instruction_data)) => {
+            Configure::try_from((instruction_data, accounts))?.process()
+        }
+        Some((Freeze::DISCRIMINATOR, _)) => Freeze::try_from(accounts)?.process(),
+        Some((Thaw::DISCRIMINATOR, _)) => Thaw::try_from(accounts)?.process(),
+        Some((ApproveDelegate::DISCRIMINATOR, instruction_data)) => {
+            ApproveDelegate::try_from((instruction_data, accounts))?.process()
+        }
+        Some((RevokeDelegate::DISCRIMINATOR, _)) => RevokeDelegate::try_from(accounts)?.process(),
+        Some((ConfigureRateLimit::DISCRIMINATOR, instruction_data)) => {
+            ConfigureRateLimit::try_from((instruction_data, accounts))?.process()
+        }
+        Some((Migrate::DISCRIMINATOR, _)) => Migrate::try_from(accounts)?.process(),
+        Some((InitializeConfig::DISCRIMINATOR, instruction_data)) => {
+            InitializeConfig::try_from((instruction_data, accounts))?.process()
+        }
+        Some((UpdateConfig::DISCRIMINATOR, instruction_data)) => {
+            UpdateConfig::try_from((instruction_data, accounts))?.process()
+        }
+        Some((InitializeMultisig::DISCRIMINATOR, instruction_data)) => {
+            InitializeMultisig::try_from((instruction_data, accounts))?.process()
+        }
+        Some((CreateSigned::DISCRIMINATOR, instruction_data)) => {
+            CreateSigned::try_from((instruction_data, accounts))?.process()
+        }
+        Some((CreateWide::DISCRIMINATOR, instruction_data)) => {
+            CreateWide::try_from((instruction_data, accounts))?.process()
+        }
+        Some((BatchIncrease::DISCRIMINATOR, instruction_data)) => {
+            BatchIncrease::try_from((instruction_data, accounts))?.process()
+        }
+        Some((CreateTally::DISCRIMINATOR, instruction_data)) => {
+            CreateTally::try_from((instruction_data, accounts))?.process()
+        }
+        Some((IncrementTally::DISCRIMINATOR, instruction_data)) => {
+            IncrementTally::try_from((instruction_data, accounts))?.process()
+        }
+        Some((ResetTally::DISCRIMINATOR, _)) => ResetTally::try_from(accounts)?.process(),
+        Some((InitializeHistory::DISCRIMINATOR, _)) => {
+            InitializeHistory::try_from(accounts)?.process()
+        }
         _ => Err(ProgramError::InvalidInstructionData),
     }
 }
@@ -0,0 +1,156 @@
+use pinocchio::program_error::{ProgramError, ToStr};
+
+/// Errors returned by the counter program, surfaced on-chain as
+/// `ProgramError::Custom(code)`.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CounterError {
+    /// The account is not the PDA derived from the counter's seeds.
+    InvalidPda = 0,
+    /// The signer is not the counter's authority.
+    InvalidAuthority = 1,
+    /// The account does not hold an initialized counter.
+    NotInitialized = 2,
+    /// The account already holds data and cannot be created again.
+    AlreadyInitialized = 3,
+    /// The operation would take the counter value above `u64::MAX`.
+    ArithmeticOverflow = 4,
+    /// The operation would take the counter value below zero.
+    ArithmeticUnderflow = 5,
+    /// The signer is not the pending authority of the counter.
+    NoPendingAuthority = 6,
+    /// The counter seed is longer than allowed.
+    InvalidSeed = 7,
+    /// The account passed as the system program is not the system program.
+    InvalidSystemProgram = 8,
+    /// Lamports cannot be sent to the account being closed.
+    InvalidDestination = 9,
+    /// The program is paused by its config and counters cannot be mutated.
+    Paused = 10,
+    /// The counter does not hold the value expected by `CompareAndSet`.
+    ValueMismatch = 11,
+    /// The operation would take the counter value below its configured minimum.
+    BelowMinimum = 12,
+    /// The operation would take the counter value above its configured maximum.
+    AboveMaximum = 13,
+    /// The amount is larger than the counter's configured maximum step.
+    StepTooLarge = 14,
+    /// The minimum is above the maximum or the maximum step is zero.
+    InvalidLimits = 15,
+    /// The counter is frozen and cannot be mutated.
+    Frozen = 16,
+    /// The delegate account does not belong to this counter and signer.
+    InvalidDelegate = 17,
+    /// The delegate's expiry slot has passed.
+    DelegateExpired = 18,
+    /// The amount is larger than the delegate's remaining allowance.
+    AllowanceExceeded = 19,
+    /// The increase violates the counter's cooldown or window maximum.
+    RateLimited = 20,
+    /// The account holds a counter version this program cannot read.
+    UnsupportedVersion = 21,
+    /// The counter has an older layout and must be migrated first.
+    OutdatedVersion = 22,
+    /// The account is not this program's ProgramData account.
+    InvalidProgramData = 23,
+    /// Fewer than `m` members of the multisig authority signed.
+    NotEnoughSigners = 24,
+    /// The multisig threshold or member list is invalid.
+    InvalidMultisig = 25,
+    /// The tally slot index or slot count is out of range.
+    InvalidSlot = 26,
+    /// The account is not the history of this counter.
+    InvalidHistory = 27,
+    /// The counter still has delegates and cannot be closed.
+    DelegatesOutstanding = 28,
+    /// The counter has a history that was not passed.
+    HistoryRequired = 29,
+}
+
+impl From<CounterError> for ProgramError {
+    fn from(error: CounterError) -> Self {
+        ProgramError::Custom(error as u32)
+    }
+}
+
+impl TryFrom<u32> for CounterError {
+    type Error = ProgramError;
+
+    fn try_from(error: u32) -> Result<Self, Self::Error> {
+        match error {
+            0 => Ok(CounterError::InvalidPda),
+            1 => Ok(CounterError::InvalidAuthority),
+            2 => Ok(CounterError::NotInitialized),
+            3 => Ok(CounterError::AlreadyInitialized),
+            4 => Ok(CounterError::ArithmeticOverflow),
+            5 => Ok(CounterError::ArithmeticUnderflow),
+            6 => Ok(CounterError::NoPendingAuthority),
+            7 => Ok(CounterError::InvalidSeed),
+            8 => Ok(CounterError::InvalidSystemProgram),
+            9 => Ok(CounterError::InvalidDestination),
+            10 => Ok(CounterError::Paused),
+            11 => Ok(CounterError::ValueMismatch),
+            12 => Ok(CounterError::BelowMinimum),
+            13 => Ok(CounterError::AboveMaximum),
+            14 => Ok(CounterError::StepTooLarge),
+            15 => Ok(CounterError::InvalidLimits),
+            16 => Ok(CounterError::Frozen),
+            17 => Ok(CounterError::InvalidDelegate),
+            18 => Ok(CounterError::DelegateExpired),
+            19 => Ok(CounterError::AllowanceExceeded),
+            20 => Ok(CounterError::RateLimited),
+            21 => Ok(CounterError::UnsupportedVersion),
+            22 => Ok(CounterError::OutdatedVersion),
+            23 => Ok(CounterError::InvalidProgramData),
+            24 => Ok(CounterError::NotEnoughSigners),
+            25 => Ok(CounterError::InvalidMultisig),
+            26 => Ok(CounterError::InvalidSlot),
+            27 => Ok(CounterError::InvalidHistory),
+            28 => Ok(CounterError::DelegatesOutstanding),
+            29 => Ok(CounterError::HistoryRequired),
+            _ => Err(ProgramError::InvalidArgument),
+        }
+    }
+}
+
+impl ToStr for CounterError {
+    fn to_str<E>(&self) -> &'static str
+    where
+        E: 'static + ToStr + TryFrom<u32>,
+    {
+        match self {
+            CounterError::InvalidPda => "Error: Account is not the expected counter PDA",
+            CounterError::InvalidAuthority => "Error: Signer is not the counter authority",
+            CounterError::NotInitialized => "Error: Counter is not initialized",
+            CounterError::AlreadyInitialized => "Error: Counter is already initialized",
+            CounterError::ArithmeticOverflow => "Error: Counter value overflow",
+            CounterError::ArithmeticUnderflow => "Error: Counter value underflow",
+            CounterError::NoPendingAuthority => "Error: Signer is not the pending authority",
+            CounterError::InvalidSeed => "Error: Counter seed is too long",
+            CounterError::InvalidSystemProgram => "Error: Invalid system program account",
+            CounterError::InvalidDestination => "Error: Invalid destination account",
+            CounterError::Paused => "Error: Program is paused",
+            CounterError::ValueMismatch => "Error: Counter value does not match the expected value",
+            CounterError::BelowMinimum => "Error: Counter value below its minimum",
+            CounterError::AboveMaximum => "Error: Counter value above its maximum",
+            CounterError::StepTooLarge => "Error: Amount exceeds the counter's maximum step",
+            CounterError::InvalidLimits => "Error: Invalid counter limits",
+            CounterError::Frozen => "Error: Counter is frozen",
+            CounterError::InvalidDelegate => "Error: Invalid delegate account",
+            CounterError::DelegateExpired => "Error: Delegate has expired",
+            CounterError::AllowanceExceeded => "Error: Amount exceeds the delegate's allowance",
+            CounterError::RateLimited => "Error: Counter increase is rate limited",
+            CounterError::UnsupportedVersion => "Error: Unsupported counter version",
+            CounterError::OutdatedVersion => "Error: Counter must be migrated first",
+            CounterError::InvalidProgramData => "Error: Invalid program data account",
+            CounterError::NotEnoughSigners => "Error: Not enough multisig signers",
+            CounterError::InvalidMultisig => "Error: Invalid multisig configuration",
+            CounterError::InvalidSlot => "Error: Tally slot out of range",
+            CounterError::InvalidHistory => "Error: Invalid counter history account",
+            CounterError::DelegatesOutstanding => {
+                "Error: Revoke the counter's delegates before closing it"
+            }
+            CounterError::HistoryRequired => "Error: Counter history account is missing",
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::state::StateKey;
+
+/// Kind of state change recorded by a [`CounterEvent`].
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EventKind {
+    Created = 0,
+    Increased = 1,
+    Decreased = 2,
+    Reset = 3,
+    Set = 4,
+    Closed = 5,
+    /// `authority` holds the proposed authority.
+    AuthorityProposed = 6,
+    /// `authority` holds the new authority.
+    AuthorityAccepted = 7,
+    Configured = 8,
+    Frozen = 9,
+    Thawed = 10,
+    /// `authority` holds the approved delegate.
+    DelegateApproved = 11,
+    /// `authority` holds the revoked delegate.
+    DelegateRevoked = 12,
+    Migrated = 13,
+    /// `counter` holds the tally; both values are zero.
+    TallyCreated = 14,
+    /// `counter` holds the tally; the values are those of the incremented
+    /// slot.
+    TallyIncremented = 15,
+    /// `counter` holds the tally; both values are zero.
+    TallyReset = 16,
+}
+
+impl TryFrom<u8> for EventKind {
+    type Error = ProgramError;
+
+    fn try_from(kind: u8) -> Result<Self, Self::Error> {
+        match kind {
+            0 => Ok(EventKind::Created),
+            1 => Ok(EventKind::Increased),
+            2 => Ok(EventKind::Decreased),
+            3 => Ok(EventKind::Reset),
+            4 => Ok(EventKind::Set),
+            5 => Ok(EventKind::Closed),
+            6 => Ok(EventKind::AuthorityProposed),
+            7 => Ok(EventKind::AuthorityAccepted),
+            8 => Ok(EventKind::Configured),
+            9 => Ok(EventKind::Frozen),
+            10 => Ok(EventKind::Thawed),
+            11 => Ok(EventKind::DelegateApproved),
+            12 => Ok(EventKind::DelegateRevoked),
+            13 => Ok(EventKind::Migrated),
+            14 => Ok(EventKind::TallyCreated),
+            15 => Ok(EventKind::TallyIncremented),
+            16 => Ok(EventKind::TallyReset),
+            _ => Err(ProgramError::InvalidArgument),
+        }
+    }
+}
+
+/// A counter value as logged in a [`CounterEvent`]: unsigned values are
+/// zero-extended and signed values sign-extended to 128 bits.
+pub trait EventValue: Copy {
+    fn to_event_value(self) -> u128;
+}
+
+impl EventValue for u64 {
+    #[inline(always)]
+    fn to_event_value(self) -> u128 {
+        self as u128
+    }
+}
+
+impl EventValue for i64 {
+    #[inline(always)]
+    fn to_event_value(self) -> u128 {
+        self as i128 as u128
+    }
+}
+
+impl EventValue for u128 {
+    #[inline(always)]
+    fn to_event_value(self) -> u128 {
+        self
+    }
+}
+
+/// Binary event logged through `sol_log_data` on every counter state change.
+///
+/// Layout (little-endian integers):
+///
+/// | offset | size | field        |
+/// | ------ | ---- | ------------ |
+/// | 0      | 1    | kind         |
+/// | 1      | 1    | counter_kind |
+/// | 2      | 32   | counter      |
+/// | 34     | 32   | authority    |
+/// | 66     | 16   | old_value    |
+/// | 82     | 16   | new_value    |
+/// | 98     | 8    | slot         |
+///
+/// `counter_kind` is the `StateKey` of the account in `counter` and tells
+/// how to read the values: those of a `SignedCounter` are `i64`s
+/// sign-extended to 128 bits, all others are unsigned.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CounterEvent {
+    pub kind: EventKind,
+    pub counter_kind: u8,
+    pub counter: Pubkey,
+    pub authority: Pubkey,
+    pub old_value: u128,
+    pub new_value: u128,
+    pub slot: u64,
+}
+
+impl CounterEvent {
+    pub const LEN: usize = 1 + 1 + 32 + 32 + 16 + 16 + 8;
+
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        let mut bytes = [0; Self::LEN];
+        bytes[0] = self.kind as u8;
+        bytes[1] = self.counter_kind;
+        bytes[2..34].copy_from_slice(&self.counter);
+        bytes[34..66].copy_from_slice(&self.authority);
+        bytes[66..82].copy_from_slice(&self.old_value.to_le_bytes());
+        bytes[82..98].copy_from_slice(&self.new_value.to_le_bytes());
+        bytes[98..106].copy_from_slice(&self.slot.to_le_bytes());
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != Self::LEN {
+            return None;
+        }
+        Some(Self {
+            kind: EventKind::try_from(bytes[0]).ok()?,
+            counter_kind: bytes[1],
+            counter: bytes[2..34].try_into().ok()?,
+            authority: bytes[34..66].try_into().ok()?,
+            old_value: u128::from_le_bytes(bytes[66..82].try_into().ok()?),
+            new_value: u128::from_le_bytes(bytes[82..98].try_into().ok()?),
+            slot: u64::from_le_bytes(bytes[98..106].try_into().ok()?),
+        })
+    }
+
+    /// Logs the event stamped with the current slot. Compiled to a no-op
+    /// when the `events` feature is disabled.
+    #[inline(always)]
+    pub fn emit<V: EventValue>(
+        kind: EventKind,
+        counter_kind: StateKey,
+        counter: &Pubkey,
+        authority: &Pubkey,
+        old_value: V,
+        new_value: V,
+    ) -> Result<(), ProgramError> {
+        #[cfg(feature = "events")]
+        {
+            use pinocchio::{
+                log::sol_log_data,
+                sysvars::{clock::Clock, Sysvar},
+            };
+
+            let event = Self {
+                kind,
+                counter_kind: counter_kind as u8,
+                counter: *counter,
+                authority: *authority,
+                old_value: old_value.to_event_value(),
+                new_value: new_value.to_event_value(),
+                slot: Clock::get()?.slot,
+            };
+            sol_log_data(&[&event.to_bytes()]);
+        }
+
+        #[cfg(not(feature = "events"))]
+        let _ = (kind, counter_kind, counter, authority, old_value, new_value);
+
+        Ok(())
+    }
+}
@@ -2,11 +2,16 @@ use pinocchio::{
     account_info::AccountInfo,
     instruction::{Seed, Signer},
     program_error::ProgramError,
-    pubkey::create_program_address,
+    pubkey::{create_program_address, find_program_address, Pubkey},
     sysvars::{rent::Rent, Sysvar},
 };
 extern crate alloc;
-use pinocchio_system::instructions::CreateAccount;
+use pinocchio_system::instructions::{Allocate, Assign, CreateAccount, Transfer};
+
+use crate::{
+    error::CounterError,
+    state::{Config, Counter, History, HistoryEntry, Limits, Multisig},
+};
 
 pub trait AccountCheck {
     fn check(account: &AccountInfo) -> Result<(), ProgramError>;
@@ -41,7 +46,7 @@ pub struct EmptyAccount;
 impl AccountCheck for EmptyAccount {
     fn check(account: &AccountInfo) -> Result<(), ProgramError> {
         if account.data_len() != 0 {
-            return Err(ProgramError::InvalidAccountData);
+            return Err(CounterError::AlreadyInitialized.into());
         }
         Ok(())
     }
@@ -52,7 +57,7 @@ pub struct SystemProgram;
 impl AccountCheck for SystemProgram {
     fn check(account: &AccountInfo) -> Result<(), ProgramError> {
         if account.key() != &pinocchio_system::ID {
-            return Err(ProgramError::InvalidAccountData);
+            return Err(CounterError::InvalidSystemProgram.into());
         }
         Ok(())
     }
@@ -76,7 +81,170 @@ impl PdaAccount {
     pub fn check(account: &AccountInfo, seeds_with_bump: &[&[u8]]) -> Result<(), ProgramError> {
         let pda_address = create_program_address(seeds_with_bump, &crate::ID)?;
         if account.key() != &pda_address {
-            return Err(ProgramError::InvalidAccountOwner);
+            return Err(CounterError::InvalidPda.into());
+        }
+        Ok(())
+    }
+}
+
+/// The program's config PDA. Passing it is what lets every mutating
+/// instruction honor the global pause.
+pub struct ConfigAccount;
+
+impl AccountCheck for ConfigAccount {
+    /// Fails unless `account` is the config PDA and the program is not
+    /// paused. An uninitialized config never pauses the program.
+    fn check(account: &AccountInfo) -> Result<(), ProgramError> {
+        if account.key() != &Config::ADDRESS {
+            return Err(CounterError::InvalidPda.into());
+        }
+
+        let data = account.try_borrow_data()?;
+        if data.is_empty() {
+            return Ok(());
+        }
+
+        ProgramAccount::check(account)?;
+        if Config::load(&data)?.is_paused() {
+            return Err(CounterError::Paused.into());
+        }
+        Ok(())
+    }
+}
+
+impl ConfigAccount {
+    /// Limits of counters created without explicit ones. `account` must
+    /// already have passed `ConfigAccount::check`.
+    pub fn default_limits(account: &AccountInfo) -> Result<Limits, ProgramError> {
+        let data = account.try_borrow_data()?;
+        if data.is_empty() {
+            return Ok(Limits::UNBOUNDED);
+        }
+        Ok(Config::load(&data)?.default_limits())
+    }
+}
+
+/// A `Multisig` authority, which signs through its members instead of its
+/// own key.
+pub struct MultisigAccount;
+
+impl MultisigAccount {
+    /// Fails unless `account` holds a `Multisig` and at least `m` of its
+    /// members are signers among `signers`.
+    pub fn check(account: &AccountInfo, signers: &[AccountInfo]) -> Result<(), ProgramError> {
+        ProgramAccount::check(account)?;
+
+        let data = account.try_borrow_data()?;
+        let multisig = Multisig::load(&data)?;
+        multisig.check_signed(
+            signers
+                .iter()
+                .filter(|signer| signer.is_signer())
+                .map(|signer| signer.key()),
+        )
+    }
+}
+
+/// A counter's optional `History`, which records its value changes.
+pub struct HistoryAccount;
+
+impl HistoryAccount {
+    /// Fails unless `account` is a writable `History` of `counter`. Only this
+    /// program writes histories, and always at their PDA, so the stored
+    /// counter is enough to tie the two together.
+    pub fn check(account: &AccountInfo, counter: &AccountInfo) -> Result<(), ProgramError> {
+        Self::check_any(account, core::slice::from_ref(counter))
+    }
+
+    /// Fails unless `account` is a writable `History` of one of `counters`.
+    pub fn check_any(account: &AccountInfo, counters: &[AccountInfo]) -> Result<(), ProgramError> {
+        WritableAccount::check(account)?;
+        ProgramAccount::check(account)?;
+
+        let data = account.try_borrow_data()?;
+        let history = History::load(&data)?;
+        if !counters
+            .iter()
+            .any(|counter| counter.key() == &history.counter)
+        {
+            return Err(CounterError::InvalidHistory.into());
+        }
+        Ok(())
+    }
+
+    /// The `History` of `counter` among `histories`, which must already have
+    /// passed `HistoryAccount::check_any`.
+    pub fn find<'a>(
+        histories: &'a [AccountInfo],
+        counter: &AccountInfo,
+    ) -> Option<&'a AccountInfo> {
+        histories.iter().find(|history| {
+            history.try_borrow_data().is_ok_and(|data| {
+                History::load(&data).is_ok_and(|history| &history.counter == counter.key())
+            })
+        })
+    }
+
+    /// Appends the change of `counter` to its history `account`, which must
+    /// already have passed `HistoryAccount::check`. Fails if the counter has
+    /// a history that was not passed.
+    pub fn record(
+        account: Option<&AccountInfo>,
+        counter: &Counter,
+        slot: u64,
+        delta: i128,
+    ) -> Result<(), ProgramError> {
+        match account {
+            Some(account) => {
+                let mut data = account.try_borrow_mut_data()?;
+                History::load_mut(data.as_mut(), true)?.append(HistoryEntry::new(
+                    slot,
+                    delta,
+                    counter.value(),
+                ));
+            }
+            None if counter.has_history() => return Err(CounterError::HistoryRequired.into()),
+            None => {}
+        }
+        Ok(())
+    }
+}
+
+pub struct ProgramDataAccount;
+
+impl ProgramDataAccount {
+    /// Owner of upgradeable programs and their ProgramData accounts.
+    pub const LOADER_ID: Pubkey =
+        pinocchio_pubkey::pubkey!("BPFLoaderUpgradeab1e11111111111111111111111");
+
+    /// Size of the `UpgradeableLoaderState::ProgramData` header: a `u32`
+    /// tag, the deployment slot and an `Option<Pubkey>` upgrade authority.
+    const HEADER_LEN: usize = 4 + 8 + 1 + 32;
+
+    /// Fails unless `account` is this program's ProgramData account and
+    /// `authority` is its upgrade authority.
+    pub fn check_upgrade_authority(
+        account: &AccountInfo,
+        authority: &AccountInfo,
+    ) -> Result<(), ProgramError> {
+        if !account.is_owned_by(&Self::LOADER_ID) {
+            return Err(CounterError::InvalidProgramData.into());
+        }
+        let (program_data_address, _) =
+            find_program_address(&[crate::ID.as_ref()], &Self::LOADER_ID);
+        if account.key() != &program_data_address {
+            return Err(CounterError::InvalidProgramData.into());
+        }
+
+        // `3` is the `ProgramData` variant of `UpgradeableLoaderState`.
+        let data = account.try_borrow_data()?;
+        if data.len() < Self::HEADER_LEN || data[0..4] != 3u32.to_le_bytes() {
+            return Err(CounterError::InvalidProgramData.into());
+        }
+        // A program without an upgrade authority is immutable and has no one
+        // allowed to initialize its config.
+        if data[12] != 1 || &data[13..Self::HEADER_LEN] != authority.key().as_ref() {
+            return Err(CounterError::InvalidAuthority.into());
         }
         Ok(())
     }
@@ -98,14 +266,74 @@ impl ProgramAccountInit for ProgramAccount {
         seeds: &[Seed],
         space: usize,
     ) -> Result<(), ProgramError> {
-        CreateAccount {
-            from: payer,
-            to: account,
-            lamports: Rent::get()?.minimum_balance(space),
-            space: space as u64,
-            owner: &crate::ID,
-        }
-        .invoke_signed(&[Signer::from(seeds)])?;
+        let lamports = Rent::get()?.minimum_balance(space);
+        let signers = [Signer::from(seeds)];
+
+        if account.lamports() == 0 {
+            CreateAccount {
+                from: payer,
+                to: account,
+                lamports,
+                space: space as u64,
+                owner: &crate::ID,
+            }
+            .invoke_signed(&signers)?;
+        } else {
+            // `CreateAccount` fails on an address that already holds lamports,
+            // which anyone can cause with a plain transfer. Top up to rent
+            // exemption and allocate/assign the account ourselves instead.
+            let required_lamports = lamports.saturating_sub(account.lamports());
+            if required_lamports > 0 {
+                Transfer {
+                    from: payer,
+                    to: account,
+                    lamports: required_lamports,
+                }
+                .invoke()?;
+            }
+
+            Allocate {
+                account,
+                space: space as u64,
+            }
+            .invoke_signed(&signers)?;
+
+            Assign {
+                account,
+                owner: &crate::ID,
+            }
+            .invoke_signed(&signers)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub trait ProgramAccountClose {
+    fn close(account: &AccountInfo, destination: &AccountInfo) -> Result<(), ProgramError>;
+}
+
+impl ProgramAccountClose for ProgramAccount {
+    fn close(account: &AccountInfo, destination: &AccountInfo) -> Result<(), ProgramError> {
+        {
+            let mut data = account.try_borrow_mut_data()?;
+            data.fill(0);
+        }
+
+        {
+            let mut destination_lamports = destination.try_borrow_mut_lamports()?;
+            let mut account_lamports = account.try_borrow_mut_lamports()?;
+            *destination_lamports = destination_lamports
+                .checked_add(*account_lamports)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+            *account_lamports = 0;
+        }
+
+        // Shrink and hand the account back to the system program so the same
+        // address can be initialized again.
+        account.realloc(0, false)?;
+        unsafe { account.assign(&pinocchio_system::ID) };
+
         Ok(())
     }
 }
@@ -0,0 +1,90 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{
+    events::{CounterEvent, EventKind},
+    helpers::{
+        AccountCheck, ConfigAccount, MultisigAccount, PdaAccount, ProgramAccount, SignerAccount,
+        WritableAccount,
+    },
+    state::{AnyCounter, Counter, CounterHeader},
+};
+
+pub struct AcceptAuthorityAccounts<'a> {
+    pub counter: &'a AccountInfo,
+    pub new_authority: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for AcceptAuthorityAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [counter, new_authority, config, multisig_signers @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        ConfigAccount::check(config)?;
+
+        // A `Multisig` accepts through its members.
+        match new_authority.is_owned_by(&crate::ID) {
+            true => MultisigAccount::check(new_authority, multisig_signers)?,
+            false => SignerAccount::check(new_authority)?,
+        }
+
+        WritableAccount::check(counter)?;
+        ProgramAccount::check(counter)?;
+
+        let counter_data = counter.try_borrow_data()?;
+        let counter_account = AnyCounter::load(&counter_data)?;
+
+        PdaAccount::check(
+            counter,
+            &Counter::seeds_with_bump(
+                &counter_account.seed_authority,
+                counter_account.seed(),
+                &[counter_account.bump],
+            ),
+        )?;
+
+        Ok(Self {
+            counter,
+            new_authority,
+            config,
+        })
+    }
+}
+
+pub struct AcceptAuthority<'a> {
+    pub accounts: AcceptAuthorityAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for AcceptAuthority<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = AcceptAuthorityAccounts::try_from(accounts)?;
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> AcceptAuthority<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &7;
+
+    pub fn process(&mut self) -> Result<(), ProgramError> {
+        let mut data = self.accounts.counter.try_borrow_mut_data()?;
+        let header = CounterHeader::load_mut(data.as_mut())?;
+        header.check_not_frozen()?;
+        header.accept_authority(self.accounts.new_authority.key())?;
+        let counter = AnyCounter::load(&data)?;
+        let value = counter.event_value();
+
+        CounterEvent::emit(
+            EventKind::AuthorityAccepted,
+            counter.kind(),
+            self.accounts.counter.key(),
+            self.accounts.new_authority.key(),
+            value,
+            value,
+        )
+    }
+}
@@ -0,0 +1,177 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+};
+
+use crate::{
+    events::{CounterEvent, EventKind},
+    helpers::{
+        AccountCheck, PdaAccount, ProgramAccount, ProgramAccountInit, SignerAccount, SystemProgram,
+        WritableAccount,
+    },
+    instructions::AuthorityAccounts,
+    state::{Counter, Delegate, StateKey},
+};
+
+/// `[counter, authority, config, delegate_account, payer, system_program]`,
+/// followed by the signing members when the authority is a `Multisig`.
+pub struct ApproveDelegateAccounts<'a> {
+    pub counter: &'a AccountInfo,
+    pub authority: &'a AccountInfo,
+    pub delegate_account: &'a AccountInfo,
+    pub payer: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ApproveDelegateAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [counter, authority, config, delegate_account, payer, system_program, multisig_signers @ ..] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        AuthorityAccounts::validate(counter, authority, config, multisig_signers)?;
+
+        // The payer funds a new delegate account, which a multisig
+        // authority cannot do.
+        SignerAccount::check(payer)?;
+        WritableAccount::check(payer)?;
+        WritableAccount::check(delegate_account)?;
+        SystemProgram::check(system_program)?;
+
+        Ok(Self {
+            counter,
+            authority,
+            delegate_account,
+            payer,
+            system_program,
+        })
+    }
+}
+
+pub struct ApproveDelegateInstructionData {
+    pub delegate: Pubkey,
+    pub allowance: u64,
+    pub expiry_slot: Option<u64>,
+}
+
+impl TryFrom<&[u8]> for ApproveDelegateInstructionData {
+    type Error = ProgramError;
+
+    /// `delegate`, then `allowance` and `expiry_slot` as little-endian
+    /// `u64`s. An expiry slot of 0 never expires.
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 48 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let expiry_slot = u64::from_le_bytes(data[40..48].try_into().unwrap());
+        Ok(Self {
+            delegate: data[0..32].try_into().unwrap(),
+            allowance: u64::from_le_bytes(data[32..40].try_into().unwrap()),
+            expiry_slot: (expiry_slot != 0).then_some(expiry_slot),
+        })
+    }
+}
+
+/// Lets `delegate` increase the counter by up to `allowance` in total.
+/// Approving an existing delegate replaces its allowance and expiry.
+pub struct ApproveDelegate<'a> {
+    pub accounts: ApproveDelegateAccounts<'a>,
+    pub instruction_data: ApproveDelegateInstructionData,
+    pub delegate_bump: u8,
+    /// Whether the delegate account is created rather than updated.
+    pub new_delegate: bool,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for ApproveDelegate<'a> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (instruction_data, accounts): (&'a [u8], &'a [AccountInfo]),
+    ) -> Result<Self, Self::Error> {
+        let accounts = ApproveDelegateAccounts::try_from(accounts)?;
+        let instruction_data = ApproveDelegateInstructionData::try_from(instruction_data)?;
+
+        let new_delegate = accounts.delegate_account.data_is_empty();
+        let delegate_bump = if new_delegate {
+            let (_, delegate_bump) = find_program_address(
+                &Delegate::seeds(accounts.counter.key(), &instruction_data.delegate),
+                &crate::ID,
+            );
+
+            ProgramAccount::init::<Delegate>(
+                accounts.payer,
+                accounts.delegate_account,
+                &Delegate::signer_seeds_with_bump(
+                    accounts.counter.key(),
+                    &instruction_data.delegate,
+                    &[delegate_bump],
+                ),
+                Delegate::LEN,
+            )?;
+
+            delegate_bump
+        } else {
+            ProgramAccount::check(accounts.delegate_account)?;
+
+            let delegate_data = accounts.delegate_account.try_borrow_data()?;
+            let delegate_bump = Delegate::load(&delegate_data)?.bump;
+
+            PdaAccount::check(
+                accounts.delegate_account,
+                &Delegate::seeds_with_bump(
+                    accounts.counter.key(),
+                    &instruction_data.delegate,
+                    &[delegate_bump],
+                ),
+            )?;
+
+            delegate_bump
+        };
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+            delegate_bump,
+            new_delegate,
+        })
+    }
+}
+
+impl<'a> ApproveDelegate<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &13;
+
+    pub fn process(&mut self) -> Result<(), ProgramError> {
+        let mut data = self.accounts.delegate_account.try_borrow_mut_data()?;
+        let delegate = Delegate::load_mut(data.as_mut(), false)?;
+        delegate.set_inner(
+            *self.accounts.counter.key(),
+            self.instruction_data.delegate,
+            self.delegate_bump,
+            self.instruction_data.allowance,
+            self.instruction_data.expiry_slot,
+        );
+
+        let value = {
+            let mut counter_data = self.accounts.counter.try_borrow_mut_data()?;
+            let counter = Counter::load_mut(counter_data.as_mut(), true)?;
+            if self.new_delegate {
+                counter.add_delegate()?;
+            }
+            counter.value()
+        };
+
+        CounterEvent::emit(
+            EventKind::DelegateApproved,
+            StateKey::Counter,
+            self.accounts.counter.key(),
+            &self.instruction_data.delegate,
+            value,
+            value,
+        )
+    }
+}
@@ -0,0 +1,108 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{
+    error::CounterError,
+    helpers::{
+        AccountCheck, ConfigAccount, HistoryAccount, MultisigAccount, PdaAccount, ProgramAccount,
+        SignerAccount, WritableAccount,
+    },
+    state::{AnyCounter, Counter},
+};
+
+/// Accounts shared by every instruction that mutates an existing counter
+/// on behalf of its authority: `[counter, authority, config]`, followed by
+/// the counter's `History` when it has one, then the signing members when
+/// the authority is a `Multisig`. The counter may be of any kind;
+/// instructions that only apply to `Counter`s fail when loading it.
+pub struct AuthorityAccounts<'a> {
+    pub counter: &'a AccountInfo,
+    pub authority: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+    pub history: Option<&'a AccountInfo>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for AuthorityAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [counter, authority, config, remaining @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // Unlike the multisig signers, a history is owned by this program.
+        let (history, multisig_signers) = match remaining {
+            [history, multisig_signers @ ..] if history.is_owned_by(&crate::ID) => {
+                (Some(history), multisig_signers)
+            }
+            _ => (None, remaining),
+        };
+
+        let mut accounts = Self::validate(counter, authority, config, multisig_signers)?;
+        if let Some(history) = history {
+            HistoryAccount::check(history, counter)?;
+            accounts.history = Some(history);
+        }
+        Ok(accounts)
+    }
+}
+
+impl<'a> AuthorityAccounts<'a> {
+    /// Validates the accounts of instructions that take further accounts
+    /// between `config` and the multisig signers. These never record to the
+    /// counter's history.
+    pub fn validate(
+        counter: &'a AccountInfo,
+        authority: &'a AccountInfo,
+        config: &'a AccountInfo,
+        multisig_signers: &'a [AccountInfo],
+    ) -> Result<Self, ProgramError> {
+        ConfigAccount::check(config)?;
+
+        // Only a `Multisig` authority is owned by this program.
+        let is_multisig = authority.is_owned_by(&crate::ID);
+        if !is_multisig {
+            SignerAccount::check(authority)?;
+        }
+
+        Self::check_counter(counter, authority)?;
+
+        if is_multisig {
+            MultisigAccount::check(authority, multisig_signers)?;
+        }
+
+        Ok(Self {
+            counter,
+            authority,
+            config,
+            history: None,
+        })
+    }
+
+    /// Fails unless `counter` is a writable counter PDA of any kind whose
+    /// current authority is `authority`.
+    pub fn check_counter(
+        counter: &AccountInfo,
+        authority: &AccountInfo,
+    ) -> Result<(), ProgramError> {
+        WritableAccount::check(counter)?;
+        ProgramAccount::check(counter)?;
+
+        let counter_data = counter.try_borrow_data()?;
+        // Hint: Can be optimized to just load the bump.
+        let counter_account = AnyCounter::load(&counter_data)?;
+
+        PdaAccount::check(
+            counter,
+            &Counter::seeds_with_bump(
+                &counter_account.seed_authority,
+                counter_account.seed(),
+                &[counter_account.bump],
+            ),
+        )?;
+
+        if &counter_account.authority != authority.key() {
+            return Err(CounterError::InvalidAuthority.into());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,162 @@
+use core::mem::size_of;
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+};
+
+use crate::{
+    events::{CounterEvent, EventKind},
+    helpers::{AccountCheck, ConfigAccount, HistoryAccount, SignerAccount},
+    instructions::AuthorityAccounts,
+    return_data::BatchValues,
+    state::{Counter, StateKey},
+};
+
+/// `[counters @ .., authority, config, histories @ ..]`, with one counter
+/// per amount. The histories of the counters that have one follow in any
+/// order. The authority signs once for every
+/// counter, so it cannot be a `Multisig`.
+pub struct BatchIncreaseAccounts<'a> {
+    pub counters: &'a [AccountInfo],
+    pub authority: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+    pub histories: &'a [AccountInfo],
+}
+
+impl<'a> BatchIncreaseAccounts<'a> {
+    /// Validates the accounts of a batch of `count` counters.
+    pub fn validate(accounts: &'a [AccountInfo], count: usize) -> Result<Self, ProgramError> {
+        if accounts.len() < count + 2 {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+        let (counters, remaining) = accounts.split_at(count);
+        let [authority, config, histories @ ..] = remaining else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        ConfigAccount::check(config)?;
+
+        SignerAccount::check(authority)?;
+
+        for counter in counters {
+            AuthorityAccounts::check_counter(counter, authority)?;
+        }
+
+        for history in histories {
+            HistoryAccount::check_any(history, counters)?;
+        }
+
+        Ok(Self {
+            counters,
+            authority,
+            config,
+            histories,
+        })
+    }
+}
+
+pub struct BatchIncreaseInstructionData<'a> {
+    /// Little-endian `u64` amounts, one per counter.
+    amounts: &'a [u8],
+}
+
+impl<'a> TryFrom<&'a [u8]> for BatchIncreaseInstructionData<'a> {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.is_empty() || data.len() % size_of::<u64>() != 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(Self { amounts: data })
+    }
+}
+
+impl BatchIncreaseInstructionData<'_> {
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.amounts.len() / size_of::<u64>()
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.amounts.is_empty()
+    }
+
+    #[inline(always)]
+    pub fn amounts(&self) -> impl Iterator<Item = u64> + '_ {
+        self.amounts
+            .chunks_exact(size_of::<u64>())
+            .map(|amount| u64::from_le_bytes(amount.try_into().unwrap()))
+    }
+}
+
+/// Increases each counter by the amount at the same position. Every
+/// counter's limits and rate limit apply as for `Increase`, and any failure
+/// aborts the whole batch. Only `Counter`s can be increased in a batch. The
+/// new values are published as `BatchValues`.
+pub struct BatchIncrease<'a> {
+    pub accounts: BatchIncreaseAccounts<'a>,
+    pub instruction_data: BatchIncreaseInstructionData<'a>,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for BatchIncrease<'a> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (instruction_data, accounts): (&'a [u8], &'a [AccountInfo]),
+    ) -> Result<Self, Self::Error> {
+        let instruction_data = BatchIncreaseInstructionData::try_from(instruction_data)?;
+        if instruction_data.len() > BatchValues::MAX_LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let accounts = BatchIncreaseAccounts::validate(accounts, instruction_data.len())?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> BatchIncrease<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &22;
+
+    pub fn process(&mut self) -> Result<(), ProgramError> {
+        let clock = Clock::get()?;
+        let mut values = BatchValues::default();
+
+        for (counter_info, amount) in self
+            .accounts
+            .counters
+            .iter()
+            .zip(self.instruction_data.amounts())
+        {
+            let mut data = counter_info.try_borrow_mut_data()?;
+            let counter = Counter::load_mut(data.as_mut(), true)?;
+            counter.check_not_frozen()?;
+            counter.rate_limit_increase(amount, clock.slot, clock.unix_timestamp)?;
+            let previous = counter.value();
+            counter.increase_by(amount)?;
+            counter.record_update(clock.slot, clock.unix_timestamp);
+            HistoryAccount::record(
+                HistoryAccount::find(self.accounts.histories, counter_info),
+                counter,
+                clock.slot,
+                amount as i128,
+            )?;
+
+            CounterEvent::emit(
+                EventKind::Increased,
+                StateKey::Counter,
+                counter_info.key(),
+                self.accounts.authority.key(),
+                previous,
+                counter.value(),
+            )?;
+            values.push(counter.value());
+        }
+
+        values.set_return_data();
+        Ok(())
+    }
+}
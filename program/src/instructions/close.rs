@@ -0,0 +1,107 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{
+    error::CounterError,
+    events::{CounterEvent, EventKind},
+    helpers::{AccountCheck, HistoryAccount, ProgramAccount, ProgramAccountClose, WritableAccount},
+    instructions::AuthorityAccounts,
+    state::AnyCounter,
+};
+
+/// `[counter, authority, config, destination]`, followed by the counter's
+/// `History` when it has one, then the signing members when the authority
+/// is a `Multisig`.
+pub struct CloseAccounts<'a> {
+    pub counter: &'a AccountInfo,
+    pub authority: &'a AccountInfo,
+    pub destination: &'a AccountInfo,
+    pub history: Option<&'a AccountInfo>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CloseAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [counter, authority, config, destination, remaining @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // Unlike the multisig signers, a history is owned by this program.
+        let (history, multisig_signers) = match remaining {
+            [history, multisig_signers @ ..] if history.is_owned_by(&crate::ID) => {
+                (Some(history), multisig_signers)
+            }
+            _ => (None, remaining),
+        };
+
+        AuthorityAccounts::validate(counter, authority, config, multisig_signers)?;
+
+        WritableAccount::check(destination)?;
+
+        if destination.key() == counter.key()
+            || history.is_some_and(|history| destination.key() == history.key())
+        {
+            return Err(CounterError::InvalidDestination.into());
+        }
+
+        if let Some(history) = history {
+            HistoryAccount::check(history, counter)?;
+        }
+
+        Ok(Self {
+            counter,
+            authority,
+            destination,
+            history,
+        })
+    }
+}
+
+/// Closes the counter, along with its `History` when it has one. A
+/// `Counter` with delegates has to revoke them first.
+pub struct Close<'a> {
+    pub accounts: CloseAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for Close<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = CloseAccounts::try_from(accounts)?;
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> Close<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &5;
+
+    pub fn process(&mut self) -> Result<(), ProgramError> {
+        let (kind, value) = {
+            let data = self.accounts.counter.try_borrow_data()?;
+            let counter = AnyCounter::load(&data)?;
+            counter.check_not_frozen()?;
+            if let AnyCounter::Counter(counter) = counter {
+                counter.check_no_delegates()?;
+                if counter.has_history() && self.accounts.history.is_none() {
+                    return Err(CounterError::HistoryRequired.into());
+                }
+            }
+            (counter.kind(), counter.event_value())
+        };
+
+        if let Some(history) = self.accounts.history {
+            ProgramAccount::close(history, self.accounts.destination)?;
+        }
+
+        ProgramAccount::close(self.accounts.counter, self.accounts.destination)?;
+
+        CounterEvent::emit(
+            EventKind::Closed,
+            kind,
+            self.accounts.counter.key(),
+            self.accounts.authority.key(),
+            value,
+            0,
+        )
+    }
+}
@@ -0,0 +1,92 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+};
+
+use crate::{
+    events::{CounterEvent, EventKind},
+    helpers::HistoryAccount,
+    instructions::AuthorityAccounts,
+    return_data::ValueChange,
+    state::{Counter, StateKey},
+};
+
+pub struct CompareAndSetInstructionData {
+    pub expected: u64,
+    pub new: u64,
+}
+
+impl TryFrom<&[u8]> for CompareAndSetInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 16 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let (expected, new) = data.split_at(8);
+        Ok(Self {
+            expected: u64::from_le_bytes(expected.try_into().unwrap()),
+            new: u64::from_le_bytes(new.try_into().unwrap()),
+        })
+    }
+}
+
+/// Sets the counter to `new` only if it currently holds `expected`. Only
+/// applies to `Counter`s, as both values are `u64`s.
+pub struct CompareAndSet<'a> {
+    pub accounts: AuthorityAccounts<'a>,
+    pub instruction_data: CompareAndSetInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for CompareAndSet<'a> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (instruction_data, accounts): (&'a [u8], &'a [AccountInfo]),
+    ) -> Result<Self, Self::Error> {
+        let accounts = AuthorityAccounts::try_from(accounts)?;
+        let instruction_data = CompareAndSetInstructionData::try_from(instruction_data)?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> CompareAndSet<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &9;
+
+    pub fn process(&mut self) -> Result<(), ProgramError> {
+        let clock = Clock::get()?;
+
+        let mut data = self.accounts.counter.try_borrow_mut_data()?;
+        let counter = Counter::load_mut(data.as_mut(), true)?;
+        counter.check_not_frozen()?;
+        let previous = counter.value();
+        counter.compare_and_set(self.instruction_data.expected, self.instruction_data.new)?;
+        counter.record_update(clock.slot, clock.unix_timestamp);
+        HistoryAccount::record(
+            self.accounts.history,
+            counter,
+            clock.slot,
+            counter.value() as i128 - previous as i128,
+        )?;
+
+        CounterEvent::emit(
+            EventKind::Set,
+            StateKey::Counter,
+            self.accounts.counter.key(),
+            self.accounts.authority.key(),
+            previous,
+            counter.value(),
+        )?;
+
+        ValueChange {
+            previous,
+            new: counter.value(),
+        }
+        .set_return_data();
+        Ok(())
+    }
+}
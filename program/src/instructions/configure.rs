@@ -0,0 +1,61 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{
+    events::{CounterEvent, EventKind},
+    instructions::AuthorityAccounts,
+    state::{Counter, Limits, StateKey},
+};
+
+pub struct ConfigureInstructionData {
+    pub limits: Limits,
+}
+
+impl TryFrom<&[u8]> for ConfigureInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        let limits = Limits::try_from(data)?;
+        Ok(Self { limits })
+    }
+}
+
+/// Replaces the counter's `min`, `max` and `max_step`.
+pub struct Configure<'a> {
+    pub accounts: AuthorityAccounts<'a>,
+    pub instruction_data: ConfigureInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Configure<'a> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (instruction_data, accounts): (&'a [u8], &'a [AccountInfo]),
+    ) -> Result<Self, Self::Error> {
+        let accounts = AuthorityAccounts::try_from(accounts)?;
+        let instruction_data = ConfigureInstructionData::try_from(instruction_data)?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> Configure<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &10;
+
+    pub fn process(&mut self) -> Result<(), ProgramError> {
+        let mut data = self.accounts.counter.try_borrow_mut_data()?;
+        let counter = Counter::load_mut(data.as_mut(), true)?;
+        counter.check_not_frozen()?;
+        counter.configure(&self.instruction_data.limits)?;
+
+        CounterEvent::emit(
+            EventKind::Configured,
+            StateKey::Counter,
+            self.accounts.counter.key(),
+            self.accounts.authority.key(),
+            counter.value(),
+            counter.value(),
+        )
+    }
+}
@@ -0,0 +1,64 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{
+    events::{CounterEvent, EventKind},
+    instructions::AuthorityAccounts,
+    state::{AnyCounter, CounterHeader, RateLimit},
+};
+
+pub struct ConfigureRateLimitInstructionData {
+    pub rate_limit: RateLimit,
+}
+
+impl TryFrom<&[u8]> for ConfigureRateLimitInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        let rate_limit = RateLimit::try_from(data)?;
+        Ok(Self { rate_limit })
+    }
+}
+
+/// Replaces the counter's cooldown and window policy. The current window is
+/// kept, so lowering `window_max` applies immediately.
+pub struct ConfigureRateLimit<'a> {
+    pub accounts: AuthorityAccounts<'a>,
+    pub instruction_data: ConfigureRateLimitInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for ConfigureRateLimit<'a> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (instruction_data, accounts): (&'a [u8], &'a [AccountInfo]),
+    ) -> Result<Self, Self::Error> {
+        let accounts = AuthorityAccounts::try_from(accounts)?;
+        let instruction_data = ConfigureRateLimitInstructionData::try_from(instruction_data)?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> ConfigureRateLimit<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &15;
+
+    pub fn process(&mut self) -> Result<(), ProgramError> {
+        let mut data = self.accounts.counter.try_borrow_mut_data()?;
+        let header = CounterHeader::load_mut(data.as_mut())?;
+        header.check_not_frozen()?;
+        header.set_rate_limit(&self.instruction_data.rate_limit);
+        let counter = AnyCounter::load(&data)?;
+        let value = counter.event_value();
+
+        CounterEvent::emit(
+            EventKind::Configured,
+            counter.kind(),
+            self.accounts.counter.key(),
+            self.accounts.authority.key(),
+            value,
+            value,
+        )
+    }
+}
@@ -1,18 +1,24 @@
 use pinocchio::{
-    account_info::AccountInfo, program_error::ProgramError, pubkey::find_program_address,
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{find_program_address, MAX_SEED_LEN},
+    sysvars::{clock::Clock, Sysvar},
 };
 
 use crate::{
+    error::CounterError,
+    events::{CounterEvent, EventKind},
     helpers::{
-        AccountCheck, EmptyAccount, ProgramAccount, ProgramAccountInit, SignerAccount,
-        SystemProgram, WritableAccount,
+        AccountCheck, ConfigAccount, EmptyAccount, ProgramAccount, ProgramAccountInit,
+        SignerAccount, SystemProgram, WritableAccount,
     },
-    state::Counter,
+    state::{Counter, Limits, StateKey},
 };
 
 pub struct CreateAccounts<'a> {
     pub counter: &'a AccountInfo,
     pub authority: &'a AccountInfo,
+    pub config: &'a AccountInfo,
     pub system_program: &'a AccountInfo,
 }
 
@@ -20,10 +26,12 @@ impl<'a> TryFrom<&'a [AccountInfo]> for CreateAccounts<'a> {
     type Error = ProgramError;
 
     fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
-        let [counter, authority, system_program] = accounts else {
+        let [counter, authority, config, system_program] = accounts else {
             return Err(ProgramError::NotEnoughAccountKeys);
         };
 
+        ConfigAccount::check(config)?;
+
         SignerAccount::check(authority)?;
         WritableAccount::check(authority)?;
 
@@ -35,34 +43,83 @@ impl<'a> TryFrom<&'a [AccountInfo]> for CreateAccounts<'a> {
         Ok(Self {
             counter,
             authority,
+            config,
             system_program,
         })
     }
 }
 
+pub struct CreateInstructionData<'a> {
+    pub seed: &'a [u8],
+    pub limits: Option<Limits>,
+}
+
+impl<'a> TryFrom<&'a [u8]> for CreateInstructionData<'a> {
+    type Error = ProgramError;
+
+    /// Empty data creates the default counter with the config's default
+    /// limits. Otherwise the data is `seed_len: u8`, the seed, and
+    /// optionally the encoded `Limits`.
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let Some((seed_len, data)) = data.split_first() else {
+            return Ok(Self {
+                seed: &[],
+                limits: None,
+            });
+        };
+
+        let seed_len = *seed_len as usize;
+        if seed_len > MAX_SEED_LEN {
+            return Err(CounterError::InvalidSeed.into());
+        }
+        if data.len() < seed_len {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let (seed, limits) = data.split_at(seed_len);
+        let limits = match limits.is_empty() {
+            true => None,
+            false => Some(Limits::try_from(limits)?),
+        };
+
+        Ok(Self { seed, limits })
+    }
+}
+
 pub struct Create<'a> {
     pub accounts: CreateAccounts<'a>,
+    pub instruction_data: CreateInstructionData<'a>,
     pub counter_bump: u8,
 }
 
-impl<'a> TryFrom<&'a [AccountInfo]> for Create<'a> {
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Create<'a> {
     type Error = ProgramError;
 
-    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+    fn try_from(
+        (instruction_data, accounts): (&'a [u8], &'a [AccountInfo]),
+    ) -> Result<Self, Self::Error> {
         let accounts = CreateAccounts::try_from(accounts)?;
+        let instruction_data = CreateInstructionData::try_from(instruction_data)?;
 
-        let (_, counter_bump) =
-            find_program_address(&Counter::seeds(accounts.authority.key()), &crate::ID);
+        let (_, counter_bump) = find_program_address(
+            &Counter::seeds(accounts.authority.key(), instruction_data.seed),
+            &crate::ID,
+        );
 
         ProgramAccount::init::<Counter>(
             accounts.authority,
             accounts.counter,
-            &Counter::signer_seeds_with_bump(accounts.authority.key(), &[counter_bump]),
+            &Counter::signer_seeds_with_bump(
+                accounts.authority.key(),
+                instruction_data.seed,
+                &[counter_bump],
+            ),
             Counter::LEN,
         )?;
 
         Ok(Self {
             accounts,
+            instruction_data,
             counter_bump,
         })
     }
@@ -74,7 +131,29 @@ impl<'a> Create<'a> {
     pub fn process(&mut self) -> Result<(), ProgramError> {
         let mut data = self.accounts.counter.try_borrow_mut_data()?;
         let counter = Counter::load_mut(data.as_mut(), false)?;
-        counter.set_inner(*self.accounts.authority.key(), self.counter_bump);
-        Ok(())
+        counter.set_inner(
+            *self.accounts.authority.key(),
+            self.counter_bump,
+            self.instruction_data.seed,
+        );
+        let clock = Clock::get()?;
+        counter.touch(clock.slot, clock.unix_timestamp);
+        // Without explicit limits the config's defaults apply. A bounded
+        // counter starts at its minimum.
+        let limits = match self.instruction_data.limits {
+            Some(limits) => limits,
+            None => ConfigAccount::default_limits(self.accounts.config)?,
+        };
+        counter.set_limits(&limits);
+        counter.set_value(limits.min);
+
+        CounterEvent::emit(
+            EventKind::Created,
+            StateKey::Counter,
+            self.accounts.counter.key(),
+            self.accounts.authority.key(),
+            0,
+            counter.value(),
+        )
     }
 }
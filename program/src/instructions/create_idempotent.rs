@@ -0,0 +1,66 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{
+    helpers::{AccountCheck, ConfigAccount, PdaAccount, ProgramAccount, SignerAccount},
+    instructions::{Create, CreateInstructionData},
+    state::Counter,
+};
+
+/// Same accounts and data as `Create`, but succeeds without changes when the
+/// authority's counter already exists, so it can be put in front of other
+/// instructions in the same transaction.
+pub struct CreateIdempotent<'a> {
+    /// `None` when a valid counter is already present.
+    pub create: Option<Create<'a>>,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for CreateIdempotent<'a> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (instruction_data, accounts): (&'a [u8], &'a [AccountInfo]),
+    ) -> Result<Self, Self::Error> {
+        let [counter, authority, config, _system_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        if counter.data_is_empty() {
+            let create = Create::try_from((instruction_data, accounts))?;
+            return Ok(Self {
+                create: Some(create),
+            });
+        }
+
+        let instruction_data = CreateInstructionData::try_from(instruction_data)?;
+
+        ConfigAccount::check(config)?;
+
+        SignerAccount::check(authority)?;
+        ProgramAccount::check(counter)?;
+
+        let counter_data = counter.try_borrow_data()?;
+        let counter_account = Counter::load(&counter_data)?;
+
+        PdaAccount::check(
+            counter,
+            &Counter::seeds_with_bump(
+                authority.key(),
+                instruction_data.seed,
+                &[counter_account.bump],
+            ),
+        )?;
+
+        Ok(Self { create: None })
+    }
+}
+
+impl<'a> CreateIdempotent<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &8;
+
+    pub fn process(&mut self) -> Result<(), ProgramError> {
+        match self.create.as_mut() {
+            Some(create) => create.process(),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use core::marker::PhantomData;
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::find_program_address,
+    sysvars::{clock::Clock, Sysvar},
+};
+
+use crate::{
+    events::{CounterEvent, EventKind},
+    helpers::{ProgramAccount, ProgramAccountInit},
+    instructions::{CreateAccounts, CreateInstructionData},
+    state::{Counter, CounterKind, SignedCounter, WideCounter},
+};
+
+/// Creates a counter of kind `T` at the same PDA a `Counter` with that seed
+/// would use. Takes the accounts of `Create` and its data without limits.
+pub struct CreateKind<'a, T: CounterKind> {
+    pub accounts: CreateAccounts<'a>,
+    pub instruction_data: CreateInstructionData<'a>,
+    pub counter_bump: u8,
+    kind: PhantomData<T>,
+}
+
+/// Creates a `SignedCounter`.
+pub type CreateSigned<'a> = CreateKind<'a, SignedCounter>;
+
+/// Creates a `WideCounter`.
+pub type CreateWide<'a> = CreateKind<'a, WideCounter>;
+
+impl<'a, T: CounterKind> TryFrom<(&'a [u8], &'a [AccountInfo])> for CreateKind<'a, T> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (instruction_data, accounts): (&'a [u8], &'a [AccountInfo]),
+    ) -> Result<Self, Self::Error> {
+        let accounts = CreateAccounts::try_from(accounts)?;
+        let instruction_data = CreateInstructionData::try_from(instruction_data)?;
+        if instruction_data.limits.is_some() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let (_, counter_bump) = find_program_address(
+            &Counter::seeds(accounts.authority.key(), instruction_data.seed),
+            &crate::ID,
+        );
+
+        ProgramAccount::init::<T>(
+            accounts.authority,
+            accounts.counter,
+            &Counter::signer_seeds_with_bump(
+                accounts.authority.key(),
+                instruction_data.seed,
+                &[counter_bump],
+            ),
+            T::LEN,
+        )?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+            counter_bump,
+            kind: PhantomData,
+        })
+    }
+}
+
+impl<'a> CreateKind<'a, SignedCounter> {
+    pub const DISCRIMINATOR: &'a u8 = &20;
+}
+
+impl<'a> CreateKind<'a, WideCounter> {
+    pub const DISCRIMINATOR: &'a u8 = &21;
+}
+
+impl<T: CounterKind> CreateKind<'_, T> {
+    pub fn process(&mut self) -> Result<(), ProgramError> {
+        let mut data = self.accounts.counter.try_borrow_mut_data()?;
+        let counter = T::load_mut(data.as_mut(), false)?;
+        counter.set_inner(
+            *self.accounts.authority.key(),
+            self.counter_bump,
+            self.instruction_data.seed,
+        );
+        let clock = Clock::get()?;
+        counter.touch(clock.slot, clock.unix_timestamp);
+
+        CounterEvent::emit(
+            EventKind::Created,
+            T::KEY,
+            self.accounts.counter.key(),
+            self.accounts.authority.key(),
+            counter.value(),
+            counter.value(),
+        )
+    }
+}
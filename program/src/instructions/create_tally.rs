@@ -0,0 +1,138 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{find_program_address, MAX_SEED_LEN},
+};
+
+use crate::{
+    error::CounterError,
+    events::{CounterEvent, EventKind},
+    helpers::{
+        AccountCheck, ConfigAccount, EmptyAccount, ProgramAccount, ProgramAccountInit,
+        SignerAccount, SystemProgram, WritableAccount,
+    },
+    state::{StateKey, Tally},
+};
+
+pub struct CreateTallyAccounts<'a> {
+    pub tally: &'a AccountInfo,
+    pub authority: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CreateTallyAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [tally, authority, config, system_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        ConfigAccount::check(config)?;
+
+        SignerAccount::check(authority)?;
+        WritableAccount::check(authority)?;
+
+        EmptyAccount::check(tally)?;
+        WritableAccount::check(tally)?;
+
+        SystemProgram::check(system_program)?;
+
+        Ok(Self {
+            tally,
+            authority,
+            config,
+            system_program,
+        })
+    }
+}
+
+pub struct CreateTallyInstructionData<'a> {
+    pub slot_count: u16,
+    pub seed: &'a [u8],
+}
+
+impl<'a> TryFrom<&'a [u8]> for CreateTallyInstructionData<'a> {
+    type Error = ProgramError;
+
+    /// `slot_count: u16` followed by the seed of the tally address.
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let Some((slot_count, seed)) = data.split_first_chunk::<2>() else {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+        let slot_count = u16::from_le_bytes(*slot_count);
+        if slot_count == 0 || slot_count > Tally::MAX_SLOTS {
+            return Err(CounterError::InvalidSlot.into());
+        }
+        if seed.len() > MAX_SEED_LEN {
+            return Err(CounterError::InvalidSeed.into());
+        }
+        Ok(Self { slot_count, seed })
+    }
+}
+
+/// Creates a `Tally` with `slot_count` zeroed slots at
+/// `[b"tally", authority, seed]`.
+pub struct CreateTally<'a> {
+    pub accounts: CreateTallyAccounts<'a>,
+    pub instruction_data: CreateTallyInstructionData<'a>,
+    pub tally_bump: u8,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for CreateTally<'a> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (instruction_data, accounts): (&'a [u8], &'a [AccountInfo]),
+    ) -> Result<Self, Self::Error> {
+        let accounts = CreateTallyAccounts::try_from(accounts)?;
+        let instruction_data = CreateTallyInstructionData::try_from(instruction_data)?;
+
+        let (_, tally_bump) = find_program_address(
+            &Tally::seeds(accounts.authority.key(), instruction_data.seed),
+            &crate::ID,
+        );
+
+        ProgramAccount::init::<Tally>(
+            accounts.authority,
+            accounts.tally,
+            &Tally::signer_seeds_with_bump(
+                accounts.authority.key(),
+                instruction_data.seed,
+                &[tally_bump],
+            ),
+            Tally::space(instruction_data.slot_count),
+        )?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+            tally_bump,
+        })
+    }
+}
+
+impl<'a> CreateTally<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &23;
+
+    pub fn process(&mut self) -> Result<(), ProgramError> {
+        let mut data = self.accounts.tally.try_borrow_mut_data()?;
+        let (tally, _) = Tally::load_mut(data.as_mut(), false)?;
+        tally.set_inner(
+            *self.accounts.authority.key(),
+            self.tally_bump,
+            self.instruction_data.seed,
+            self.instruction_data.slot_count,
+        );
+
+        CounterEvent::emit(
+            EventKind::TallyCreated,
+            StateKey::Tally,
+            self.accounts.tally.key(),
+            self.accounts.authority.key(),
+            0u64,
+            0,
+        )
+    }
+}
@@ -0,0 +1,86 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+};
+
+use crate::{
+    events::{CounterEvent, EventKind},
+    instructions::AuthorityAccounts,
+    return_data::KindValueChange,
+    state::{AnyCounter, Counter, CounterKind, SignedCounter, StateKey, WideCounter},
+};
+
+pub struct DecreaseInstructionData {
+    pub amount: u64,
+}
+
+impl TryFrom<&[u8]> for DecreaseInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        let amount = u64::from_le_bytes(
+            data.try_into()
+                .map_err(|_| ProgramError::InvalidInstructionData)?,
+        );
+        Ok(Self { amount })
+    }
+}
+
+pub struct Decrease<'a> {
+    pub accounts: AuthorityAccounts<'a>,
+    pub instruction_data: DecreaseInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Decrease<'a> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (instruction_data, accounts): (&'a [u8], &'a [AccountInfo]),
+    ) -> Result<Self, Self::Error> {
+        let accounts = AuthorityAccounts::try_from(accounts)?;
+        let instruction_data = DecreaseInstructionData::try_from(instruction_data)?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> Decrease<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &2;
+
+    pub fn process(&mut self) -> Result<(), ProgramError> {
+        let kind = AnyCounter::load(&self.accounts.counter.try_borrow_data()?)?.kind();
+        match kind {
+            StateKey::SignedCounter => self.process_kind::<SignedCounter>(),
+            StateKey::WideCounter => self.process_kind::<WideCounter>(),
+            _ => self.process_kind::<Counter>(),
+        }
+    }
+
+    /// Only `Counter`s have limits and a history.
+    fn process_kind<T: CounterKind>(&mut self) -> Result<(), ProgramError> {
+        let clock = Clock::get()?;
+
+        let mut data = self.accounts.counter.try_borrow_mut_data()?;
+        let counter = T::load_mut(data.as_mut(), true)?;
+        counter.check_not_frozen()?;
+        let previous = counter.value();
+        counter.decrease_by(self.instruction_data.amount)?;
+        counter.record_update(clock.slot, clock.unix_timestamp);
+        counter.record_history(self.accounts.history, clock.slot, previous)?;
+
+        CounterEvent::emit(
+            EventKind::Decreased,
+            T::KEY,
+            self.accounts.counter.key(),
+            self.accounts.authority.key(),
+            previous,
+            counter.value(),
+        )?;
+
+        T::ValueChange::new(previous, counter.value()).set_return_data();
+        Ok(())
+    }
+}
@@ -0,0 +1,82 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{
+    error::CounterError,
+    helpers::{
+        AccountCheck, ConfigAccount, HistoryAccount, PdaAccount, ProgramAccount, SignerAccount,
+        WritableAccount,
+    },
+    state::{Counter, Delegate},
+};
+
+/// Accounts of an instruction signed by one of the counter's delegates
+/// instead of its authority: `[counter, delegate, config, delegate_account]`,
+/// followed by the counter's `History` when it has one.
+pub struct DelegateAccounts<'a> {
+    pub counter: &'a AccountInfo,
+    pub delegate: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+    pub delegate_account: &'a AccountInfo,
+    pub history: Option<&'a AccountInfo>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for DelegateAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [counter, delegate, config, delegate_account, history @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        let history = match history {
+            [] => None,
+            [history] => Some(history),
+            _ => return Err(ProgramError::InvalidArgument),
+        };
+
+        ConfigAccount::check(config)?;
+
+        SignerAccount::check(delegate)?;
+
+        WritableAccount::check(counter)?;
+        ProgramAccount::check(counter)?;
+
+        let counter_data = counter.try_borrow_data()?;
+        let counter_account = Counter::load(&counter_data)?;
+
+        PdaAccount::check(
+            counter,
+            &Counter::seeds_with_bump(
+                &counter_account.seed_authority,
+                counter_account.seed(),
+                &[counter_account.bump],
+            ),
+        )?;
+
+        WritableAccount::check(delegate_account)?;
+        ProgramAccount::check(delegate_account)?;
+
+        let delegate_data = delegate_account.try_borrow_data()?;
+        let delegate_state = Delegate::load(&delegate_data)?;
+
+        if &delegate_state.counter != counter.key() || &delegate_state.delegate != delegate.key() {
+            return Err(CounterError::InvalidDelegate.into());
+        }
+
+        PdaAccount::check(
+            delegate_account,
+            &Delegate::seeds_with_bump(counter.key(), delegate.key(), &[delegate_state.bump]),
+        )?;
+
+        if let Some(history) = history {
+            HistoryAccount::check(history, counter)?;
+        }
+
+        Ok(Self {
+            counter,
+            delegate,
+            config,
+            delegate_account,
+            history,
+        })
+    }
+}
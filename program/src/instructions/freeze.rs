@@ -0,0 +1,41 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{
+    events::{CounterEvent, EventKind},
+    instructions::AuthorityAccounts,
+    state::{AnyCounter, CounterHeader, CounterStatus},
+};
+
+/// Stops every mutation of the counter until it is thawed.
+pub struct Freeze<'a> {
+    pub accounts: AuthorityAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for Freeze<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = AuthorityAccounts::try_from(accounts)?;
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> Freeze<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &11;
+
+    pub fn process(&mut self) -> Result<(), ProgramError> {
+        let mut data = self.accounts.counter.try_borrow_mut_data()?;
+        CounterHeader::load_mut(data.as_mut())?.set_status(CounterStatus::Frozen);
+        let counter = AnyCounter::load(&data)?;
+        let value = counter.event_value();
+
+        CounterEvent::emit(
+            EventKind::Frozen,
+            counter.kind(),
+            self.accounts.counter.key(),
+            self.accounts.authority.key(),
+            value,
+            value,
+        )
+    }
+}
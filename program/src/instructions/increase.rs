@@ -1,39 +1,72 @@
-use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+};
 
 use crate::{
-    helpers::{AccountCheck, PdaAccount, ProgramAccount, WritableAccount},
-    state::Counter,
+    events::{CounterEvent, EventKind},
+    instructions::{AuthorityAccounts, DelegateAccounts},
+    return_data::KindValueChange,
+    state::{AnyCounter, Counter, CounterKind, Delegate, SignedCounter, StateKey, WideCounter},
 };
 
-pub struct IncreaseAccounts<'a> {
-    pub counter: &'a AccountInfo,
-    pub authority: &'a AccountInfo,
+/// `AuthorityAccounts`, or `DelegateAccounts` to increase on the delegate's
+/// allowance.
+pub enum IncreaseAccounts<'a> {
+    Authority(AuthorityAccounts<'a>),
+    Delegate(DelegateAccounts<'a>),
 }
 
 impl<'a> TryFrom<&'a [AccountInfo]> for IncreaseAccounts<'a> {
     type Error = ProgramError;
 
     fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
-        let [counter, authority] = accounts else {
-            return Err(ProgramError::NotEnoughAccountKeys);
-        };
-
-        WritableAccount::check(counter)?;
-        ProgramAccount::check(counter)?;
+        // Neither a history nor multisig signers are `Delegate` accounts.
+        match accounts {
+            [_, _, _, delegate_account, ..] if is_delegate_account(delegate_account) => {
+                Ok(Self::Delegate(DelegateAccounts::try_from(accounts)?))
+            }
+            _ => Ok(Self::Authority(AuthorityAccounts::try_from(accounts)?)),
+        }
+    }
+}
 
-        let counter_data = counter.try_borrow_data()?;
-        // Hint: Can be optimized to just load the bump.
-        let counter_account = Counter::load(&counter_data)?;
+impl<'a> IncreaseAccounts<'a> {
+    #[inline(always)]
+    pub fn counter(&self) -> &'a AccountInfo {
+        match self {
+            Self::Authority(accounts) => accounts.counter,
+            Self::Delegate(accounts) => accounts.counter,
+        }
+    }
 
-        PdaAccount::check(
-            counter,
-            &Counter::seeds_with_bump(authority.key(), &[counter_account.bump]),
-        )?;
+    #[inline(always)]
+    pub fn history(&self) -> Option<&'a AccountInfo> {
+        match self {
+            Self::Authority(accounts) => accounts.history,
+            Self::Delegate(accounts) => accounts.history,
+        }
+    }
 
-        Ok(Self { counter, authority })
+    /// The authority or delegate that signed the instruction.
+    #[inline(always)]
+    pub fn signer(&self) -> &'a AccountInfo {
+        match self {
+            Self::Authority(accounts) => accounts.authority,
+            Self::Delegate(accounts) => accounts.delegate,
+        }
     }
 }
 
+#[inline(always)]
+fn is_delegate_account(account: &AccountInfo) -> bool {
+    account.is_owned_by(&crate::ID)
+        && account
+            .try_borrow_data()
+            .is_ok_and(|data| data.first() == Some(&(StateKey::Delegate as u8)))
+}
+
 pub struct IncreaseInstructionData {
     pub amount: u64,
 }
@@ -74,9 +107,48 @@ impl<'a> Increase<'a> {
     pub const DISCRIMINATOR: &'a u8 = &1;
 
     pub fn process(&mut self) -> Result<(), ProgramError> {
-        let mut data = self.accounts.counter.try_borrow_mut_data()?;
-        let counter = Counter::load_mut(data.as_mut(), true)?;
+        let kind = AnyCounter::load(&self.accounts.counter().try_borrow_data()?)?.kind();
+        match kind {
+            StateKey::SignedCounter => self.process_kind::<SignedCounter>(),
+            StateKey::WideCounter => self.process_kind::<WideCounter>(),
+            _ => self.process_kind::<Counter>(),
+        }
+    }
+
+    /// Only `Counter`s have limits, delegates and a history, so the other
+    /// kinds are always increased by their authority and never recorded.
+    fn process_kind<T: CounterKind>(&mut self) -> Result<(), ProgramError> {
+        let clock = Clock::get()?;
+
+        if let IncreaseAccounts::Delegate(accounts) = &self.accounts {
+            let mut data = accounts.delegate_account.try_borrow_mut_data()?;
+            let delegate = Delegate::load_mut(data.as_mut(), true)?;
+            delegate.draw(self.instruction_data.amount, clock.slot)?;
+        }
+
+        let mut data = self.accounts.counter().try_borrow_mut_data()?;
+        let counter = T::load_mut(data.as_mut(), true)?;
+        counter.check_not_frozen()?;
+        counter.rate_limit_increase(
+            self.instruction_data.amount,
+            clock.slot,
+            clock.unix_timestamp,
+        )?;
+        let previous = counter.value();
         counter.increase_by(self.instruction_data.amount)?;
+        counter.record_update(clock.slot, clock.unix_timestamp);
+        counter.record_history(self.accounts.history(), clock.slot, previous)?;
+
+        CounterEvent::emit(
+            EventKind::Increased,
+            T::KEY,
+            self.accounts.counter().key(),
+            self.accounts.signer().key(),
+            previous,
+            counter.value(),
+        )?;
+
+        T::ValueChange::new(previous, counter.value()).set_return_data();
         Ok(())
     }
 }
@@ -0,0 +1,78 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{
+    events::{CounterEvent, EventKind},
+    instructions::TallyAuthorityAccounts,
+    return_data::ValueChange,
+    state::{StateKey, Tally},
+};
+
+pub struct IncrementTallyInstructionData {
+    pub index: u16,
+    pub amount: u64,
+}
+
+impl TryFrom<&[u8]> for IncrementTallyInstructionData {
+    type Error = ProgramError;
+
+    /// `index: u16` followed by `amount: u64`.
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 10 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(Self {
+            index: u16::from_le_bytes(data[0..2].try_into().unwrap()),
+            amount: u64::from_le_bytes(data[2..10].try_into().unwrap()),
+        })
+    }
+}
+
+/// Adds `amount` to slot `index` of a tally. The slot's change is
+/// published as a `ValueChange`.
+pub struct IncrementTally<'a> {
+    pub accounts: TallyAuthorityAccounts<'a>,
+    pub instruction_data: IncrementTallyInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for IncrementTally<'a> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (instruction_data, accounts): (&'a [u8], &'a [AccountInfo]),
+    ) -> Result<Self, Self::Error> {
+        let accounts = TallyAuthorityAccounts::try_from(accounts)?;
+        let instruction_data = IncrementTallyInstructionData::try_from(instruction_data)?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> IncrementTally<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &24;
+
+    pub fn process(&mut self) -> Result<(), ProgramError> {
+        let mut data = self.accounts.tally.try_borrow_mut_data()?;
+        let (_, slots) = Tally::load_mut(data.as_mut(), true)?;
+        let previous = Tally::slot(slots, self.instruction_data.index)?;
+        Tally::increment(
+            slots,
+            self.instruction_data.index,
+            self.instruction_data.amount,
+        )?;
+        let new = Tally::slot(slots, self.instruction_data.index)?;
+
+        CounterEvent::emit(
+            EventKind::TallyIncremented,
+            StateKey::Tally,
+            self.accounts.tally.key(),
+            self.accounts.authority.key(),
+            previous,
+            new,
+        )?;
+
+        ValueChange { previous, new }.set_return_data();
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{
+    error::CounterError,
+    helpers::{
+        AccountCheck, EmptyAccount, ProgramAccount, ProgramAccountInit, ProgramDataAccount,
+        SignerAccount, SystemProgram, WritableAccount,
+    },
+    state::Config,
+};
+
+pub struct InitializeConfigAccounts<'a> {
+    pub config: &'a AccountInfo,
+    /// Upgrade authority of the program, which also pays for the config.
+    pub upgrade_authority: &'a AccountInfo,
+    pub program_data: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for InitializeConfigAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [config, upgrade_authority, program_data, system_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(upgrade_authority)?;
+        WritableAccount::check(upgrade_authority)?;
+
+        ProgramDataAccount::check_upgrade_authority(program_data, upgrade_authority)?;
+
+        EmptyAccount::check(config)?;
+        WritableAccount::check(config)?;
+
+        SystemProgram::check(system_program)?;
+
+        Ok(Self {
+            config,
+            upgrade_authority,
+            program_data,
+            system_program,
+        })
+    }
+}
+
+pub struct InitializeConfigInstructionData {
+    pub admin: Pubkey,
+    pub default_max_step: u64,
+}
+
+impl TryFrom<&[u8]> for InitializeConfigInstructionData {
+    type Error = ProgramError;
+
+    /// `admin: Pubkey` followed by `default_max_step: u64`.
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 40 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let default_max_step = u64::from_le_bytes(data[32..40].try_into().unwrap());
+        if default_max_step == 0 {
+            return Err(CounterError::InvalidLimits.into());
+        }
+        Ok(Self {
+            admin: data[0..32].try_into().unwrap(),
+            default_max_step,
+        })
+    }
+}
+
+/// Creates the singleton `Config`. Only the program's upgrade authority may
+/// do so, and the program starts unpaused.
+pub struct InitializeConfig<'a> {
+    pub accounts: InitializeConfigAccounts<'a>,
+    pub instruction_data: InitializeConfigInstructionData,
+    pub config_bump: u8,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for InitializeConfig<'a> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (instruction_data, accounts): (&'a [u8], &'a [AccountInfo]),
+    ) -> Result<Self, Self::Error> {
+        let accounts = InitializeConfigAccounts::try_from(accounts)?;
+        let instruction_data = InitializeConfigInstructionData::try_from(instruction_data)?;
+
+        let config_bump = Config::BUMP;
+
+        ProgramAccount::init::<Config>(
+            accounts.upgrade_authority,
+            accounts.config,
+            &Config::signer_seeds_with_bump(&[config_bump]),
+            Config::LEN,
+        )?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+            config_bump,
+        })
+    }
+}
+
+impl<'a> InitializeConfig<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &17;
+
+    pub fn process(&mut self) -> Result<(), ProgramError> {
+        let mut data = self.accounts.config.try_borrow_mut_data()?;
+        let config = Config::load_mut(data.as_mut(), false)?;
+        config.set_inner(
+            self.instruction_data.admin,
+            self.config_bump,
+            self.instruction_data.default_max_step,
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+use pinocchio::{
+    account_info::AccountInfo, program_error::ProgramError, pubkey::find_program_address,
+};
+
+use crate::{
+    error::CounterError,
+    helpers::{
+        AccountCheck, EmptyAccount, HistoryAccount, ProgramAccount, ProgramAccountInit,
+        SignerAccount, SystemProgram, WritableAccount,
+    },
+    instructions::AuthorityAccounts,
+    state::{Counter, History},
+};
+
+/// `[counter, authority, config, history, payer, system_program]`, followed
+/// by the signing members when the authority is a `Multisig`.
+pub struct InitializeHistoryAccounts<'a> {
+    pub counter: &'a AccountInfo,
+    pub authority: &'a AccountInfo,
+    pub history: &'a AccountInfo,
+    pub payer: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for InitializeHistoryAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [counter, authority, config, history, payer, system_program, multisig_signers @ ..] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        AuthorityAccounts::validate(counter, authority, config, multisig_signers)?;
+
+        // Entries hold `u64` values, so only `Counter`s have a history.
+        let has_history = Counter::load(&counter.try_borrow_data()?)?.has_history();
+
+        // The payer funds the history, which a multisig authority cannot do.
+        SignerAccount::check(payer)?;
+        WritableAccount::check(payer)?;
+
+        // A history left behind by an earlier counter at the same address is
+        // reset rather than created.
+        if history.is_owned_by(&crate::ID) {
+            if has_history {
+                return Err(CounterError::AlreadyInitialized.into());
+            }
+            HistoryAccount::check(history, counter)?;
+        } else {
+            EmptyAccount::check(history)?;
+            WritableAccount::check(history)?;
+        }
+
+        SystemProgram::check(system_program)?;
+
+        Ok(Self {
+            counter,
+            authority,
+            history,
+            payer,
+            system_program,
+        })
+    }
+}
+
+/// Creates the counter's empty `History` at `[b"history", counter]`. From
+/// then on, every instruction that changes the counter's value has to be
+/// passed the history and records the change.
+pub struct InitializeHistory<'a> {
+    pub accounts: InitializeHistoryAccounts<'a>,
+    pub history_bump: u8,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for InitializeHistory<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = InitializeHistoryAccounts::try_from(accounts)?;
+
+        let (_, history_bump) =
+            find_program_address(&History::seeds(accounts.counter.key()), &crate::ID);
+
+        if accounts.history.data_len() == 0 {
+            ProgramAccount::init::<History>(
+                accounts.payer,
+                accounts.history,
+                &History::signer_seeds_with_bump(accounts.counter.key(), &[history_bump]),
+                History::LEN,
+            )?;
+        }
+
+        Ok(Self {
+            accounts,
+            history_bump,
+        })
+    }
+}
+
+impl<'a> InitializeHistory<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &26;
+
+    pub fn process(&mut self) -> Result<(), ProgramError> {
+        let mut data = self.accounts.history.try_borrow_mut_data()?;
+        let history = History::load_mut(data.as_mut(), false)?;
+        history.set_inner(*self.accounts.counter.key(), self.history_bump);
+
+        let mut data = self.accounts.counter.try_borrow_mut_data()?;
+        Counter::load_mut(data.as_mut(), true)?.set_has_history(true);
+        Ok(())
+    }
+}
@@ -0,0 +1,123 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{find_program_address, MAX_SEED_LEN},
+};
+
+use crate::{
+    error::CounterError,
+    helpers::{
+        AccountCheck, EmptyAccount, ProgramAccount, ProgramAccountInit, SignerAccount,
+        SystemProgram, WritableAccount,
+    },
+    state::Multisig,
+};
+
+pub struct InitializeMultisigAccounts<'a> {
+    pub multisig: &'a AccountInfo,
+    /// Pays for the multisig and seeds its address.
+    pub creator: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    /// Members of the set; they do not need to sign.
+    pub signers: &'a [AccountInfo],
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for InitializeMultisigAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [multisig, creator, system_program, signers @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(creator)?;
+        WritableAccount::check(creator)?;
+
+        EmptyAccount::check(multisig)?;
+        WritableAccount::check(multisig)?;
+
+        SystemProgram::check(system_program)?;
+
+        Ok(Self {
+            multisig,
+            creator,
+            system_program,
+            signers,
+        })
+    }
+}
+
+pub struct InitializeMultisigInstructionData<'a> {
+    pub m: u8,
+    pub seed: &'a [u8],
+}
+
+impl<'a> TryFrom<&'a [u8]> for InitializeMultisigInstructionData<'a> {
+    type Error = ProgramError;
+
+    /// `m: u8` followed by the seed of the multisig address.
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let Some((m, seed)) = data.split_first() else {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+        if seed.len() > MAX_SEED_LEN {
+            return Err(CounterError::InvalidSeed.into());
+        }
+        Ok(Self { m: *m, seed })
+    }
+}
+
+/// Creates an m-of-n `Multisig` at `[b"multisig", creator, seed]` whose
+/// address can then be proposed as a counter's authority.
+pub struct InitializeMultisig<'a> {
+    pub accounts: InitializeMultisigAccounts<'a>,
+    pub instruction_data: InitializeMultisigInstructionData<'a>,
+    pub multisig_bump: u8,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for InitializeMultisig<'a> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (instruction_data, accounts): (&'a [u8], &'a [AccountInfo]),
+    ) -> Result<Self, Self::Error> {
+        let accounts = InitializeMultisigAccounts::try_from(accounts)?;
+        let instruction_data = InitializeMultisigInstructionData::try_from(instruction_data)?;
+
+        let (_, multisig_bump) = find_program_address(
+            &Multisig::seeds(accounts.creator.key(), instruction_data.seed),
+            &crate::ID,
+        );
+
+        ProgramAccount::init::<Multisig>(
+            accounts.creator,
+            accounts.multisig,
+            &Multisig::signer_seeds_with_bump(
+                accounts.creator.key(),
+                instruction_data.seed,
+                &[multisig_bump],
+            ),
+            Multisig::LEN,
+        )?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+            multisig_bump,
+        })
+    }
+}
+
+impl<'a> InitializeMultisig<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &19;
+
+    pub fn process(&mut self) -> Result<(), ProgramError> {
+        let mut data = self.accounts.multisig.try_borrow_mut_data()?;
+        let multisig = Multisig::load_mut(data.as_mut(), false)?;
+        multisig.set_inner(
+            self.multisig_bump,
+            self.instruction_data.m,
+            self.accounts.signers.iter().map(|signer| signer.key()),
+        )
+    }
+}
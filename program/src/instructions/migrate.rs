@@ -0,0 +1,123 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, rent::Rent, Sysvar},
+};
+use pinocchio_system::instructions::Transfer;
+
+use crate::{
+    events::{CounterEvent, EventKind},
+    helpers::{
+        AccountCheck, ConfigAccount, PdaAccount, ProgramAccount, SignerAccount, SystemProgram,
+        WritableAccount,
+    },
+    state::{Counter, StateKey, VersionedCounter},
+};
+
+pub struct MigrateAccounts<'a> {
+    pub counter: &'a AccountInfo,
+    pub payer: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for MigrateAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [counter, payer, config, system_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        ConfigAccount::check(config)?;
+
+        // Migration preserves every field, so anyone may pay for it.
+        SignerAccount::check(payer)?;
+        WritableAccount::check(payer)?;
+
+        WritableAccount::check(counter)?;
+        ProgramAccount::check(counter)?;
+
+        SystemProgram::check(system_program)?;
+
+        let counter_data = counter.try_borrow_data()?;
+        let versioned_counter = VersionedCounter::load(&counter_data)?;
+
+        PdaAccount::check(
+            counter,
+            &Counter::seeds_with_bump(
+                versioned_counter.seed_authority(),
+                versioned_counter.seed(),
+                &[versioned_counter.bump()],
+            ),
+        )?;
+
+        Ok(Self {
+            counter,
+            payer,
+            config,
+            system_program,
+        })
+    }
+}
+
+/// Rewrites a counter of an older version into the current `Counter`
+/// layout, reallocating it and topping up its rent from the payer. Counters
+/// that are already current are left untouched.
+pub struct Migrate<'a> {
+    pub accounts: MigrateAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for Migrate<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = MigrateAccounts::try_from(accounts)?;
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> Migrate<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &16;
+
+    pub fn process(&mut self) -> Result<(), ProgramError> {
+        let (bump, authority, value) = {
+            let data = self.accounts.counter.try_borrow_data()?;
+            match VersionedCounter::load(&data)? {
+                VersionedCounter::V1(counter) => (counter.bump, counter.authority, counter.value()),
+                VersionedCounter::V2(_) => return Ok(()),
+            }
+        };
+
+        let required_lamports = Rent::get()?
+            .minimum_balance(Counter::LEN)
+            .saturating_sub(self.accounts.counter.lamports());
+        if required_lamports > 0 {
+            Transfer {
+                from: self.accounts.payer,
+                to: self.accounts.counter,
+                lamports: required_lamports,
+            }
+            .invoke()?;
+        }
+
+        self.accounts.counter.realloc(Counter::LEN, true)?;
+
+        let clock = Clock::get()?;
+
+        let mut data = self.accounts.counter.try_borrow_mut_data()?;
+        let counter = Counter::load_mut(data.as_mut(), false)?;
+        counter.set_inner(authority, bump, &[]);
+        counter.set_value(value);
+        counter.touch(clock.slot, clock.unix_timestamp);
+
+        CounterEvent::emit(
+            EventKind::Migrated,
+            StateKey::Counter,
+            self.accounts.counter.key(),
+            &authority,
+            value,
+            value,
+        )
+    }
+}
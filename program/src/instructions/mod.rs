@@ -1,5 +1,59 @@
+pub mod accept_authority;
+pub mod approve_delegate;
+pub mod authority;
+pub mod batch_increase;
+pub mod close;
+pub mod compare_and_set;
+pub mod configure;
+pub mod configure_rate_limit;
 pub mod create;
+pub mod create_idempotent;
+pub mod create_kind;
+pub mod create_tally;
+pub mod decrease;
+pub mod delegate;
+pub mod freeze;
 pub mod increase;
+pub mod increment_tally;
+pub mod initialize_config;
+pub mod initialize_history;
+pub mod initialize_multisig;
+pub mod migrate;
+pub mod propose_authority;
+pub mod reset;
+pub mod reset_tally;
+pub mod revoke_delegate;
+pub mod set;
+pub mod tally_authority;
+pub mod thaw;
+pub mod update_config;
 
+pub use accept_authority::*;
+pub use approve_delegate::*;
+pub use authority::*;
+pub use batch_increase::*;
+pub use close::*;
+pub use compare_and_set::*;
+pub use configure::*;
+pub use configure_rate_limit::*;
 pub use create::*;
+pub use create_idempotent::*;
+pub use create_kind::*;
+pub use create_tally::*;
+pub use decrease::*;
+pub use delegate::*;
+pub use freeze::*;
 pub use increase::*;
+pub use increment_tally::*;
+pub use initialize_config::*;
+pub use initialize_history::*;
+pub use initialize_multisig::*;
+pub use migrate::*;
+pub use propose_authority::*;
+pub use reset::*;
+pub use reset_tally::*;
+pub use revoke_delegate::*;
+pub use set::*;
+pub use tally_authority::*;
+pub use thaw::*;
+pub use update_config::*;
@@ -0,0 +1,67 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{
+    events::{CounterEvent, EventKind},
+    instructions::AuthorityAccounts,
+    state::{AnyCounter, CounterHeader},
+};
+
+pub struct ProposeAuthorityInstructionData {
+    pub new_authority: Pubkey,
+}
+
+impl TryFrom<&[u8]> for ProposeAuthorityInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        let new_authority: Pubkey = data
+            .try_into()
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+        Ok(Self { new_authority })
+    }
+}
+
+/// Proposes a new authority for the counter. The transfer only takes effect
+/// once the proposed key signs `AcceptAuthority`; proposing the default
+/// pubkey cancels a pending proposal.
+pub struct ProposeAuthority<'a> {
+    pub accounts: AuthorityAccounts<'a>,
+    pub instruction_data: ProposeAuthorityInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for ProposeAuthority<'a> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (instruction_data, accounts): (&'a [u8], &'a [AccountInfo]),
+    ) -> Result<Self, Self::Error> {
+        let accounts = AuthorityAccounts::try_from(accounts)?;
+        let instruction_data = ProposeAuthorityInstructionData::try_from(instruction_data)?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> ProposeAuthority<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &6;
+
+    pub fn process(&mut self) -> Result<(), ProgramError> {
+        let mut data = self.accounts.counter.try_borrow_mut_data()?;
+        let header = CounterHeader::load_mut(data.as_mut())?;
+        header.check_not_frozen()?;
+        header.propose_authority(self.instruction_data.new_authority);
+        let counter = AnyCounter::load(&data)?;
+        let value = counter.event_value();
+
+        CounterEvent::emit(
+            EventKind::AuthorityProposed,
+            counter.kind(),
+            self.accounts.counter.key(),
+            &self.instruction_data.new_authority,
+            value,
+            value,
+        )
+    }
+}
@@ -0,0 +1,65 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+};
+
+use crate::{
+    events::{CounterEvent, EventKind},
+    instructions::AuthorityAccounts,
+    return_data::KindValueChange,
+    state::{AnyCounter, Counter, CounterKind, SignedCounter, StateKey, WideCounter},
+};
+
+/// Moves the counter back to its lower bound, which is zero for the kinds
+/// without limits.
+pub struct Reset<'a> {
+    pub accounts: AuthorityAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for Reset<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = AuthorityAccounts::try_from(accounts)?;
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> Reset<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &3;
+
+    pub fn process(&mut self) -> Result<(), ProgramError> {
+        let kind = AnyCounter::load(&self.accounts.counter.try_borrow_data()?)?.kind();
+        match kind {
+            StateKey::SignedCounter => self.process_kind::<SignedCounter>(),
+            StateKey::WideCounter => self.process_kind::<WideCounter>(),
+            _ => self.process_kind::<Counter>(),
+        }
+    }
+
+    /// Only `Counter`s have a history.
+    fn process_kind<T: CounterKind>(&mut self) -> Result<(), ProgramError> {
+        let clock = Clock::get()?;
+
+        let mut data = self.accounts.counter.try_borrow_mut_data()?;
+        let counter = T::load_mut(data.as_mut(), true)?;
+        counter.check_not_frozen()?;
+        let previous = counter.value();
+        counter.reset();
+        counter.record_update(clock.slot, clock.unix_timestamp);
+        counter.record_history(self.accounts.history, clock.slot, previous)?;
+
+        CounterEvent::emit(
+            EventKind::Reset,
+            T::KEY,
+            self.accounts.counter.key(),
+            self.accounts.authority.key(),
+            previous,
+            counter.value(),
+        )?;
+
+        T::ValueChange::new(previous, counter.value()).set_return_data();
+        Ok(())
+    }
+}
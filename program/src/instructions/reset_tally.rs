@@ -0,0 +1,40 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{
+    events::{CounterEvent, EventKind},
+    instructions::TallyAuthorityAccounts,
+    state::{StateKey, Tally},
+};
+
+/// Sets every slot of a tally back to zero.
+pub struct ResetTally<'a> {
+    pub accounts: TallyAuthorityAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ResetTally<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = TallyAuthorityAccounts::try_from(accounts)?;
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> ResetTally<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &25;
+
+    pub fn process(&mut self) -> Result<(), ProgramError> {
+        let mut data = self.accounts.tally.try_borrow_mut_data()?;
+        let (_, slots) = Tally::load_mut(data.as_mut(), true)?;
+        Tally::reset(slots);
+
+        CounterEvent::emit(
+            EventKind::TallyReset,
+            StateKey::Tally,
+            self.accounts.tally.key(),
+            self.accounts.authority.key(),
+            0u64,
+            0,
+        )
+    }
+}
@@ -0,0 +1,101 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{
+    error::CounterError,
+    events::{CounterEvent, EventKind},
+    helpers::{AccountCheck, PdaAccount, ProgramAccount, ProgramAccountClose, WritableAccount},
+    instructions::AuthorityAccounts,
+    state::{Counter, Delegate, StateKey},
+};
+
+/// `[counter, authority, config, delegate_account, destination]`, followed
+/// by the signing members when the authority is a `Multisig`.
+pub struct RevokeDelegateAccounts<'a> {
+    pub counter: &'a AccountInfo,
+    pub authority: &'a AccountInfo,
+    pub delegate_account: &'a AccountInfo,
+    pub destination: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for RevokeDelegateAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [counter, authority, config, delegate_account, destination, multisig_signers @ ..] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        AuthorityAccounts::validate(counter, authority, config, multisig_signers)?;
+
+        // The delegate account's rent goes to the destination.
+        WritableAccount::check(destination)?;
+        if destination.key() == delegate_account.key() {
+            return Err(CounterError::InvalidDestination.into());
+        }
+        WritableAccount::check(delegate_account)?;
+        ProgramAccount::check(delegate_account)?;
+
+        let delegate_data = delegate_account.try_borrow_data()?;
+        let delegate = Delegate::load(&delegate_data)?;
+
+        if &delegate.counter != counter.key() {
+            return Err(CounterError::InvalidDelegate.into());
+        }
+
+        PdaAccount::check(
+            delegate_account,
+            &Delegate::seeds_with_bump(counter.key(), &delegate.delegate, &[delegate.bump]),
+        )?;
+
+        Ok(Self {
+            counter,
+            authority,
+            delegate_account,
+            destination,
+        })
+    }
+}
+
+/// Closes a delegate account, sending its rent to the destination.
+pub struct RevokeDelegate<'a> {
+    pub accounts: RevokeDelegateAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for RevokeDelegate<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = RevokeDelegateAccounts::try_from(accounts)?;
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> RevokeDelegate<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &14;
+
+    pub fn process(&mut self) -> Result<(), ProgramError> {
+        let delegate = {
+            let data = self.accounts.delegate_account.try_borrow_data()?;
+            Delegate::load(&data)?.delegate
+        };
+        let value = {
+            let mut data = self.accounts.counter.try_borrow_mut_data()?;
+            let counter = Counter::load_mut(data.as_mut(), true)?;
+            counter.remove_delegate();
+            counter.value()
+        };
+
+        ProgramAccount::close(self.accounts.delegate_account, self.accounts.destination)?;
+
+        CounterEvent::emit(
+            EventKind::DelegateRevoked,
+            StateKey::Counter,
+            self.accounts.counter.key(),
+            &delegate,
+            value,
+            value,
+        )
+    }
+}
@@ -0,0 +1,88 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+};
+
+use crate::{
+    events::{CounterEvent, EventKind},
+    helpers::HistoryAccount,
+    instructions::AuthorityAccounts,
+    return_data::ValueChange,
+    state::{Counter, StateKey},
+};
+
+pub struct SetInstructionData {
+    pub value: u64,
+}
+
+impl TryFrom<&[u8]> for SetInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        let value = u64::from_le_bytes(
+            data.try_into()
+                .map_err(|_| ProgramError::InvalidInstructionData)?,
+        );
+        Ok(Self { value })
+    }
+}
+
+/// Sets the counter to the given value. Only applies to `Counter`s, as
+/// the value is a `u64`.
+pub struct Set<'a> {
+    pub accounts: AuthorityAccounts<'a>,
+    pub instruction_data: SetInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Set<'a> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (instruction_data, accounts): (&'a [u8], &'a [AccountInfo]),
+    ) -> Result<Self, Self::Error> {
+        let accounts = AuthorityAccounts::try_from(accounts)?;
+        let instruction_data = SetInstructionData::try_from(instruction_data)?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> Set<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &4;
+
+    pub fn process(&mut self) -> Result<(), ProgramError> {
+        let clock = Clock::get()?;
+
+        let mut data = self.accounts.counter.try_borrow_mut_data()?;
+        let counter = Counter::load_mut(data.as_mut(), true)?;
+        counter.check_not_frozen()?;
+        let previous = counter.value();
+        counter.set(self.instruction_data.value)?;
+        counter.record_update(clock.slot, clock.unix_timestamp);
+        HistoryAccount::record(
+            self.accounts.history,
+            counter,
+            clock.slot,
+            counter.value() as i128 - previous as i128,
+        )?;
+
+        CounterEvent::emit(
+            EventKind::Set,
+            StateKey::Counter,
+            self.accounts.counter.key(),
+            self.accounts.authority.key(),
+            previous,
+            counter.value(),
+        )?;
+
+        ValueChange {
+            previous,
+            new: counter.value(),
+        }
+        .set_return_data();
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{
+    error::CounterError,
+    helpers::{
+        AccountCheck, ConfigAccount, PdaAccount, ProgramAccount, SignerAccount, WritableAccount,
+    },
+    state::Tally,
+};
+
+/// Accounts of every instruction that mutates a tally on behalf of its
+/// authority: `[tally, authority, config]`. The authority paid for the tally
+/// and never changes, so it is always a signing key rather than a
+/// `Multisig`.
+pub struct TallyAuthorityAccounts<'a> {
+    pub tally: &'a AccountInfo,
+    pub authority: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for TallyAuthorityAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [tally, authority, config] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        ConfigAccount::check(config)?;
+
+        SignerAccount::check(authority)?;
+
+        WritableAccount::check(tally)?;
+        ProgramAccount::check(tally)?;
+
+        let tally_data = tally.try_borrow_data()?;
+        let (tally_account, _) = Tally::load(&tally_data)?;
+
+        PdaAccount::check(
+            tally,
+            &Tally::seeds_with_bump(
+                &tally_account.authority,
+                tally_account.seed(),
+                &[tally_account.bump],
+            ),
+        )?;
+
+        if &tally_account.authority != authority.key() {
+            return Err(CounterError::InvalidAuthority.into());
+        }
+
+        Ok(Self {
+            tally,
+            authority,
+            config,
+        })
+    }
+}
@@ -0,0 +1,41 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{
+    events::{CounterEvent, EventKind},
+    instructions::AuthorityAccounts,
+    state::{AnyCounter, CounterHeader, CounterStatus},
+};
+
+/// Lifts a freeze so the counter can be mutated again.
+pub struct Thaw<'a> {
+    pub accounts: AuthorityAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for Thaw<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = AuthorityAccounts::try_from(accounts)?;
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> Thaw<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &12;
+
+    pub fn process(&mut self) -> Result<(), ProgramError> {
+        let mut data = self.accounts.counter.try_borrow_mut_data()?;
+        CounterHeader::load_mut(data.as_mut())?.set_status(CounterStatus::Active);
+        let counter = AnyCounter::load(&data)?;
+        let value = counter.event_value();
+
+        CounterEvent::emit(
+            EventKind::Thawed,
+            counter.kind(),
+            self.accounts.counter.key(),
+            self.accounts.authority.key(),
+            value,
+            value,
+        )
+    }
+}
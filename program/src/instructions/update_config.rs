@@ -0,0 +1,104 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{
+    error::CounterError,
+    helpers::{AccountCheck, PdaAccount, ProgramAccount, SignerAccount, WritableAccount},
+    state::Config,
+};
+
+pub struct UpdateConfigAccounts<'a> {
+    pub config: &'a AccountInfo,
+    pub admin: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for UpdateConfigAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [config, admin] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(admin)?;
+
+        WritableAccount::check(config)?;
+        ProgramAccount::check(config)?;
+
+        let config_data = config.try_borrow_data()?;
+        let config_account = Config::load(&config_data)?;
+
+        PdaAccount::check(config, &Config::seeds_with_bump(&[config_account.bump]))?;
+
+        if &config_account.admin != admin.key() {
+            return Err(CounterError::InvalidAuthority.into());
+        }
+
+        Ok(Self { config, admin })
+    }
+}
+
+pub struct UpdateConfigInstructionData {
+    pub admin: Pubkey,
+    pub paused: bool,
+    pub default_max_step: u64,
+}
+
+impl TryFrom<&[u8]> for UpdateConfigInstructionData {
+    type Error = ProgramError;
+
+    /// `admin: Pubkey`, `paused: u8` (0 or 1) and `default_max_step: u64`.
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 41 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let paused = match data[32] {
+            0 => false,
+            1 => true,
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
+        let default_max_step = u64::from_le_bytes(data[33..41].try_into().unwrap());
+        if default_max_step == 0 {
+            return Err(CounterError::InvalidLimits.into());
+        }
+        Ok(Self {
+            admin: data[0..32].try_into().unwrap(),
+            paused,
+            default_max_step,
+        })
+    }
+}
+
+/// Replaces the config's admin, pause switch and default policies. It is
+/// the only instruction besides `InitializeConfig` allowed while paused.
+pub struct UpdateConfig<'a> {
+    pub accounts: UpdateConfigAccounts<'a>,
+    pub instruction_data: UpdateConfigInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for UpdateConfig<'a> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (instruction_data, accounts): (&'a [u8], &'a [AccountInfo]),
+    ) -> Result<Self, Self::Error> {
+        let accounts = UpdateConfigAccounts::try_from(accounts)?;
+        let instruction_data = UpdateConfigInstructionData::try_from(instruction_data)?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> UpdateConfig<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &18;
+
+    pub fn process(&mut self) -> Result<(), ProgramError> {
+        let mut data = self.accounts.config.try_borrow_mut_data()?;
+        let config = Config::load_mut(data.as_mut(), true)?;
+        config.admin = self.instruction_data.admin;
+        config.set_paused(self.instruction_data.paused);
+        config.set_default_max_step(self.instruction_data.default_max_step);
+        Ok(())
+    }
+}
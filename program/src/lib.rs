@@ -1,7 +1,10 @@
 #[cfg(not(feature = "no-entrypoint"))]
 pub mod entrypoint;
+pub mod error;
+pub mod events;
 pub mod helpers;
 pub mod instructions;
+pub mod return_data;
 pub mod state;
 
 pinocchio_pubkey::declare_id!("ctrpxur8CmT8WzNfJS3vygJmTGegsLv69bjLak6EMot");
@@ -0,0 +1,209 @@
+use core::mem::size_of;
+use pinocchio::program::{set_return_data, MAX_RETURN_DATA};
+
+/// Value change published through return data by every instruction that
+/// modifies a counter's value, so CPI callers don't need to re-read the
+/// account.
+///
+/// Encoded as the new value followed by the previous value, both
+/// little-endian `u64`s.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ValueChange {
+    pub previous: u64,
+    pub new: u64,
+}
+
+impl ValueChange {
+    pub const LEN: usize = 16;
+
+    #[inline(always)]
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        let mut bytes = [0; Self::LEN];
+        bytes[..8].copy_from_slice(&self.new.to_le_bytes());
+        bytes[8..].copy_from_slice(&self.previous.to_le_bytes());
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let bytes: &[u8; Self::LEN] = bytes.try_into().ok()?;
+        let (new, previous) = bytes.split_at(8);
+        Some(Self {
+            previous: u64::from_le_bytes(previous.try_into().ok()?),
+            new: u64::from_le_bytes(new.try_into().ok()?),
+        })
+    }
+
+    #[inline(always)]
+    pub fn set_return_data(&self) {
+        set_return_data(&self.to_bytes());
+    }
+}
+
+/// `ValueChange` of a `SignedCounter`: the new value followed by the
+/// previous value, both little-endian `i64`s.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SignedValueChange {
+    pub previous: i64,
+    pub new: i64,
+}
+
+impl SignedValueChange {
+    pub const LEN: usize = 16;
+
+    #[inline(always)]
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        let mut bytes = [0; Self::LEN];
+        bytes[..8].copy_from_slice(&self.new.to_le_bytes());
+        bytes[8..].copy_from_slice(&self.previous.to_le_bytes());
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let bytes: &[u8; Self::LEN] = bytes.try_into().ok()?;
+        let (new, previous) = bytes.split_at(8);
+        Some(Self {
+            previous: i64::from_le_bytes(previous.try_into().ok()?),
+            new: i64::from_le_bytes(new.try_into().ok()?),
+        })
+    }
+
+    #[inline(always)]
+    pub fn set_return_data(&self) {
+        set_return_data(&self.to_bytes());
+    }
+}
+
+/// `ValueChange` of a `WideCounter`: the new value followed by the previous
+/// value, both little-endian `u128`s.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WideValueChange {
+    pub previous: u128,
+    pub new: u128,
+}
+
+impl WideValueChange {
+    pub const LEN: usize = 32;
+
+    #[inline(always)]
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        let mut bytes = [0; Self::LEN];
+        bytes[..16].copy_from_slice(&self.new.to_le_bytes());
+        bytes[16..].copy_from_slice(&self.previous.to_le_bytes());
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let bytes: &[u8; Self::LEN] = bytes.try_into().ok()?;
+        let (new, previous) = bytes.split_at(16);
+        Some(Self {
+            previous: u128::from_le_bytes(previous.try_into().ok()?),
+            new: u128::from_le_bytes(new.try_into().ok()?),
+        })
+    }
+
+    #[inline(always)]
+    pub fn set_return_data(&self) {
+        set_return_data(&self.to_bytes());
+    }
+}
+
+/// Value change of a counter kind, as selected by
+/// `CounterKind::ValueChange`.
+pub trait KindValueChange {
+    type Value;
+
+    fn new(previous: Self::Value, new: Self::Value) -> Self;
+
+    fn set_return_data(&self);
+}
+
+impl KindValueChange for ValueChange {
+    type Value = u64;
+
+    #[inline(always)]
+    fn new(previous: u64, new: u64) -> Self {
+        Self { previous, new }
+    }
+
+    #[inline(always)]
+    fn set_return_data(&self) {
+        ValueChange::set_return_data(self)
+    }
+}
+
+impl KindValueChange for SignedValueChange {
+    type Value = i64;
+
+    #[inline(always)]
+    fn new(previous: i64, new: i64) -> Self {
+        Self { previous, new }
+    }
+
+    #[inline(always)]
+    fn set_return_data(&self) {
+        SignedValueChange::set_return_data(self)
+    }
+}
+
+impl KindValueChange for WideValueChange {
+    type Value = u128;
+
+    #[inline(always)]
+    fn new(previous: u128, new: u128) -> Self {
+        Self { previous, new }
+    }
+
+    #[inline(always)]
+    fn set_return_data(&self) {
+        WideValueChange::set_return_data(self)
+    }
+}
+
+/// New values of the counters of a `BatchIncrease`, in account order, as
+/// consecutive little-endian `u64`s.
+#[derive(Clone, Copy, Debug)]
+pub struct BatchValues {
+    len: usize,
+    bytes: [u8; MAX_RETURN_DATA],
+}
+
+impl Default for BatchValues {
+    fn default() -> Self {
+        Self {
+            len: 0,
+            bytes: [0; MAX_RETURN_DATA],
+        }
+    }
+}
+
+impl BatchValues {
+    /// Number of values that fit in return data.
+    pub const MAX_LEN: usize = MAX_RETURN_DATA / size_of::<u64>();
+
+    /// Appends the new value of the next counter. Values past `MAX_LEN` are
+    /// dropped.
+    #[inline(always)]
+    pub fn push(&mut self, value: u64) {
+        if let Some(bytes) = self.bytes.get_mut(self.len..self.len + size_of::<u64>()) {
+            bytes.copy_from_slice(&value.to_le_bytes());
+            self.len += size_of::<u64>();
+        }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Vec<u64>> {
+        if bytes.len() % size_of::<u64>() != 0 {
+            return None;
+        }
+        Some(
+            bytes
+                .chunks_exact(size_of::<u64>())
+                .map(|value| u64::from_le_bytes(value.try_into().unwrap()))
+                .collect(),
+        )
+    }
+
+    #[inline(always)]
+    pub fn set_return_data(&self) {
+        set_return_data(&self.bytes[..self.len]);
+    }
+}
@@ -0,0 +1,107 @@
+use core::ops::Deref;
+use pinocchio::program_error::ProgramError;
+
+use crate::{
+    error::CounterError,
+    events::EventValue,
+    state::{Counter, CounterHeader, SignedCounter, StateKey, WideCounter},
+};
+
+/// A counter account of any kind, as selected by its `StateKey`. It
+/// dereferences to the `CounterHeader` all kinds share.
+#[derive(Debug)]
+pub enum AnyCounter<'a> {
+    Counter(&'a Counter),
+    Signed(&'a SignedCounter),
+    Wide(&'a WideCounter),
+}
+
+impl<'a> AnyCounter<'a> {
+    /// Loads `bytes` with the loader of the kind stored in their key.
+    #[inline(always)]
+    pub fn load(bytes: &'a [u8]) -> Result<Self, ProgramError> {
+        match bytes.first() {
+            Some(key) if *key == StateKey::SignedCounter as u8 => {
+                SignedCounter::load(bytes).map(AnyCounter::Signed)
+            }
+            Some(key) if *key == StateKey::WideCounter as u8 => {
+                WideCounter::load(bytes).map(AnyCounter::Wide)
+            }
+            Some(key) if *key == StateKey::Counter as u8 => {
+                Counter::load(bytes).map(AnyCounter::Counter)
+            }
+            Some(_) => Err(CounterError::NotInitialized.into()),
+            None => Err(ProgramError::InvalidAccountData),
+        }
+    }
+
+    /// `StateKey` of the counter's kind.
+    #[inline(always)]
+    pub fn kind(&self) -> StateKey {
+        match self {
+            AnyCounter::Counter(_) => StateKey::Counter,
+            AnyCounter::Signed(_) => StateKey::SignedCounter,
+            AnyCounter::Wide(_) => StateKey::WideCounter,
+        }
+    }
+
+    /// The value as logged in a `CounterEvent`.
+    #[inline(always)]
+    pub fn event_value(&self) -> u128 {
+        match self {
+            AnyCounter::Counter(counter) => counter.value().to_event_value(),
+            AnyCounter::Signed(counter) => counter.value().to_event_value(),
+            AnyCounter::Wide(counter) => counter.value(),
+        }
+    }
+}
+
+impl Deref for AnyCounter<'_> {
+    type Target = CounterHeader;
+
+    #[inline(always)]
+    fn deref(&self) -> &CounterHeader {
+        match self {
+            AnyCounter::Counter(counter) => &counter.header,
+            AnyCounter::Signed(counter) => &counter.header,
+            AnyCounter::Wide(counter) => &counter.header,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_dispatches_on_key() {
+        let mut signed = [0u8; SignedCounter::LEN];
+        let counter = SignedCounter::load_mut(&mut signed, false).unwrap();
+        counter.set_inner([7; 32], 254, b"balance");
+        counter.set_value(-1);
+        let counter = AnyCounter::load(&signed).unwrap();
+        assert!(matches!(counter, AnyCounter::Signed(_)));
+        assert_eq!(counter.seed(), b"balance");
+        assert_eq!(counter.event_value(), -1i128 as u128);
+
+        let mut wide = [0u8; WideCounter::LEN];
+        let counter = WideCounter::load_mut(&mut wide, false).unwrap();
+        counter.set_inner([7; 32], 253, &[]);
+        counter.set_value(u64::MAX as u128 + 1);
+        let counter = AnyCounter::load(&wide).unwrap();
+        assert!(matches!(counter, AnyCounter::Wide(_)));
+        assert_eq!(counter.bump, 253);
+        assert_eq!(counter.event_value(), u64::MAX as u128 + 1);
+
+        // A key of one kind over the layout of another is rejected
+        wide[0] = StateKey::SignedCounter as u8;
+        assert_eq!(
+            AnyCounter::load(&wide).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+        assert_eq!(
+            AnyCounter::load(&[StateKey::Delegate as u8]).unwrap_err(),
+            CounterError::NotInitialized.into()
+        );
+    }
+}
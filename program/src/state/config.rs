@@ -0,0 +1,149 @@
+use core::mem::{align_of, size_of};
+use pinocchio::{instruction::Seed, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{
+    error::CounterError,
+    state::{Limits, StateKey},
+};
+
+/// Zero-copy view of the program's singleton config account, derived from
+/// `[b"config"]`.
+///
+/// Until `InitializeConfig` runs the PDA is empty, which means the program
+/// is not paused and new counters get `Limits::UNBOUNDED`.
+#[repr(C)]
+#[derive(Debug)]
+pub struct Config {
+    /// `StateKey` discriminator.
+    pub key: u8,
+    pub bump: u8,
+    /// Non-zero while every mutating instruction is rejected.
+    paused: u8,
+    /// Key allowed to update the config.
+    pub admin: Pubkey,
+    /// Maximum step of counters created without explicit limits.
+    default_max_step: [u8; 8],
+}
+
+const _: () = assert!(size_of::<Config>() == Config::LEN);
+const _: () = assert!(align_of::<Config>() == 1);
+
+impl Config {
+    pub const LEN: usize = size_of::<u8>()
+        + size_of::<u8>()
+        + size_of::<u8>()
+        + size_of::<Pubkey>()
+        + size_of::<u64>();
+
+    /// The config PDA, derived from `Config::seeds()` ahead of time so that
+    /// checking it on every instruction costs a key comparison.
+    pub const ADDRESS: Pubkey =
+        pinocchio_pubkey::pubkey!("6hHxTSDWxY1azM8RCMwdABBSPWbEzRqNhq1oREtb7vMZ");
+
+    /// Canonical bump of `Config::ADDRESS`.
+    pub const BUMP: u8 = 255;
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8], check_key: bool) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != Config::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if check_key && bytes[0] != StateKey::Config as u8 {
+            return Err(CounterError::NotInitialized.into());
+        }
+        // SAFETY: `Config` has alignment 1, no padding and is exactly
+        // `Config::LEN` bytes, so any byte slice of that length is valid.
+        Ok(unsafe { &mut *(bytes.as_mut_ptr() as *mut Self) })
+    }
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != Config::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if bytes[0] != StateKey::Config as u8 {
+            return Err(CounterError::NotInitialized.into());
+        }
+        // SAFETY: see `load_mut`.
+        Ok(unsafe { &*(bytes.as_ptr() as *const Self) })
+    }
+
+    #[inline(always)]
+    pub fn set_inner(&mut self, admin: Pubkey, bump: u8, default_max_step: u64) {
+        self.key = StateKey::Config as u8;
+        self.bump = bump;
+        self.set_paused(false);
+        self.admin = admin;
+        self.set_default_max_step(default_max_step);
+    }
+
+    #[inline(always)]
+    pub fn is_paused(&self) -> bool {
+        self.paused != 0
+    }
+
+    #[inline(always)]
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused as u8;
+    }
+
+    #[inline(always)]
+    pub fn default_max_step(&self) -> u64 {
+        u64::from_le_bytes(self.default_max_step)
+    }
+
+    #[inline(always)]
+    pub fn set_default_max_step(&mut self, default_max_step: u64) {
+        self.default_max_step = default_max_step.to_le_bytes();
+    }
+
+    /// Limits given to counters created without explicit ones.
+    #[inline(always)]
+    pub fn default_limits(&self) -> Limits {
+        Limits {
+            max_step: self.default_max_step(),
+            ..Limits::UNBOUNDED
+        }
+    }
+
+    #[inline(always)]
+    pub fn seeds() -> [&'static [u8]; 1] {
+        [b"config"]
+    }
+
+    #[inline(always)]
+    pub fn seeds_with_bump(bump: &[u8]) -> [&[u8]; 2] {
+        [Self::seeds()[0], bump]
+    }
+
+    #[inline(always)]
+    pub fn signer_seeds_with_bump(bump: &[u8]) -> [Seed<'_>; 2] {
+        let seeds = Self::seeds_with_bump(bump);
+        [Seed::from(seeds[0]), Seed::from(seeds[1])]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_limits_only_bound_the_step() {
+        let mut bytes = [0u8; Config::LEN];
+        let config = Config::load_mut(&mut bytes, false).unwrap();
+        config.set_inner([3; 32], 255, 10);
+        assert!(!config.is_paused());
+
+        config.set_paused(true);
+        let config = Config::load(&bytes).unwrap();
+        assert!(config.is_paused());
+        assert_eq!(
+            config.default_limits(),
+            Limits {
+                min: 0,
+                max: u64::MAX,
+                max_step: 10,
+            }
+        );
+    }
+}
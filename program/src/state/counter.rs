@@ -1,77 +1,615 @@
-use core::mem::size_of;
-use pinocchio::{instruction::Seed, program_error::ProgramError, pubkey::Pubkey};
+use core::{
+    mem::{align_of, size_of},
+    ops::{Deref, DerefMut},
+};
+use pinocchio::{
+    account_info::AccountInfo, instruction::Seed, program_error::ProgramError, pubkey::Pubkey,
+};
+
+use crate::{
+    error::CounterError,
+    helpers::HistoryAccount,
+    return_data::ValueChange,
+    state::{CounterHeader, CounterKind, CounterV1},
+};
 
 #[repr(u8)]
 #[derive(Debug)]
 pub enum StateKey {
     Uninitialized = 0,
     Counter = 1,
+    Delegate = 2,
+    Config = 3,
+    Multisig = 4,
+    SignedCounter = 5,
+    WideCounter = 6,
+    Tally = 7,
+    History = 8,
 }
 
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CounterStatus {
+    Active = 0,
+    /// Every mutating instruction fails until the counter is thawed.
+    Frozen = 1,
+}
+
+/// Zero-copy view of a counter account.
+///
+/// Every field is a byte or byte array, so the struct has an alignment of 1
+/// and no padding: it can be cast from any account data slice of exactly
+/// `Counter::LEN` bytes. Multi-byte integers are stored little-endian and
+/// read through accessors. The fields shared with the other counter kinds
+/// live in the leading `CounterHeader`, which the counter dereferences to.
 #[repr(C)]
 #[derive(Debug)]
 pub struct Counter {
-    pub key: StateKey,
-    pub bump: u8,
-    pub authority: Pubkey,
-    pub value: u64,
+    pub header: CounterHeader,
+    min: [u8; 8],
+    max: [u8; 8],
+    max_step: [u8; 8],
+    /// Number of live `Delegate` accounts of the counter.
+    delegate_count: [u8; 8],
+    /// Whether the counter has a `History`, which then has to record every
+    /// change of its value.
+    has_history: u8,
+    value: [u8; 8],
+}
+
+/// Business limits enforced on a counter's value. `Limits::UNBOUNDED` only
+/// keeps the `u64` range.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Limits {
+    pub min: u64,
+    pub max: u64,
+    /// Largest amount a single increase or decrease may apply.
+    pub max_step: u64,
+}
+
+impl Limits {
+    pub const LEN: usize = 3 * size_of::<u64>();
+
+    pub const UNBOUNDED: Self = Self {
+        min: 0,
+        max: u64::MAX,
+        max_step: u64::MAX,
+    };
+
+    /// Fails if `value` is outside `[min, max]`.
+    #[inline(always)]
+    pub fn check(&self, value: u64) -> Result<(), ProgramError> {
+        if value < self.min {
+            return Err(CounterError::BelowMinimum.into());
+        }
+        if value > self.max {
+            return Err(CounterError::AboveMaximum.into());
+        }
+        Ok(())
+    }
+
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        let mut bytes = [0; Self::LEN];
+        bytes[0..8].copy_from_slice(&self.min.to_le_bytes());
+        bytes[8..16].copy_from_slice(&self.max.to_le_bytes());
+        bytes[16..24].copy_from_slice(&self.max_step.to_le_bytes());
+        bytes
+    }
+}
+
+/// Throttles increases of a counter. A zero field disables that part of the
+/// policy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RateLimit {
+    /// Minimum number of slots between two updates.
+    pub cooldown_slots: u64,
+    /// Minimum number of seconds between two updates.
+    pub cooldown_seconds: u64,
+    /// Length of the window in which at most `window_max` may be added.
+    pub window_slots: u64,
+    pub window_max: u64,
+}
+
+impl RateLimit {
+    pub const LEN: usize = 4 * size_of::<u64>();
+
+    pub const DISABLED: Self = Self {
+        cooldown_slots: 0,
+        cooldown_seconds: 0,
+        window_slots: 0,
+        window_max: 0,
+    };
+
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        let mut bytes = [0; Self::LEN];
+        bytes[0..8].copy_from_slice(&self.cooldown_slots.to_le_bytes());
+        bytes[8..16].copy_from_slice(&self.cooldown_seconds.to_le_bytes());
+        bytes[16..24].copy_from_slice(&self.window_slots.to_le_bytes());
+        bytes[24..32].copy_from_slice(&self.window_max.to_le_bytes());
+        bytes
+    }
+}
+
+impl TryFrom<&[u8]> for RateLimit {
+    type Error = ProgramError;
+
+    /// Parses the four fields as little-endian `u64`s.
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let rate_limit = Self {
+            cooldown_slots: u64::from_le_bytes(data[0..8].try_into().unwrap()),
+            cooldown_seconds: u64::from_le_bytes(data[8..16].try_into().unwrap()),
+            window_slots: u64::from_le_bytes(data[16..24].try_into().unwrap()),
+            window_max: u64::from_le_bytes(data[24..32].try_into().unwrap()),
+        };
+        // A window needs both a length and a maximum.
+        if (rate_limit.window_slots == 0) != (rate_limit.window_max == 0) {
+            return Err(CounterError::InvalidLimits.into());
+        }
+        Ok(rate_limit)
+    }
 }
 
+impl TryFrom<&[u8]> for Limits {
+    type Error = ProgramError;
+
+    /// Parses `min`, `max` and `max_step` as little-endian `u64`s.
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let limits = Self {
+            min: u64::from_le_bytes(data[0..8].try_into().unwrap()),
+            max: u64::from_le_bytes(data[8..16].try_into().unwrap()),
+            max_step: u64::from_le_bytes(data[16..24].try_into().unwrap()),
+        };
+        if limits.min > limits.max || limits.max_step == 0 {
+            return Err(CounterError::InvalidLimits.into());
+        }
+        Ok(limits)
+    }
+}
+
+const _: () = assert!(size_of::<Counter>() == Counter::LEN);
+const _: () = assert!(align_of::<Counter>() == 1);
+
 impl Counter {
+    /// Version written by this program. `CounterV1` accounts predate the
+    /// `version` byte and are told apart by their length.
+    pub const VERSION: u8 = 2;
+
     pub const LEN: usize =
-        size_of::<StateKey>() + size_of::<u8>() + size_of::<Pubkey>() + size_of::<u64>();
+        CounterHeader::LEN + Limits::LEN + size_of::<u64>() + size_of::<u8>() + size_of::<u64>();
+
+    /// Fails unless `bytes` holds a counter of the current version. Counters
+    /// of an older version fail with `OutdatedVersion` until migrated.
+    #[inline(always)]
+    fn check_layout(bytes: &[u8]) -> Result<(), ProgramError> {
+        if bytes.len() != Counter::LEN {
+            if bytes.len() == CounterV1::LEN && bytes[0] == StateKey::Counter as u8 {
+                return Err(CounterError::OutdatedVersion.into());
+            }
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if bytes[0] != StateKey::Counter as u8 {
+            return Err(CounterError::NotInitialized.into());
+        }
+        if bytes[1] != Counter::VERSION {
+            return Err(CounterError::UnsupportedVersion.into());
+        }
+        Ok(())
+    }
 
     #[inline(always)]
     pub fn load_mut(bytes: &mut [u8], check_key: bool) -> Result<&mut Self, ProgramError> {
-        if bytes.len() != Counter::LEN || (check_key && bytes[0] != StateKey::Counter as u8) {
+        if check_key {
+            Self::check_layout(bytes)?;
+        } else if bytes.len() != Counter::LEN {
             return Err(ProgramError::InvalidAccountData);
         }
-        Ok(unsafe { &mut *core::mem::transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
+        // SAFETY: `Counter` has alignment 1, no padding and is exactly
+        // `Counter::LEN` bytes, so any byte slice of that length is valid.
+        Ok(unsafe { &mut *(bytes.as_mut_ptr() as *mut Self) })
     }
 
     #[inline(always)]
     pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
-        if bytes.len() != Counter::LEN || bytes[0] != StateKey::Counter as u8 {
-            return Err(ProgramError::InvalidAccountData);
+        Self::check_layout(bytes)?;
+        // SAFETY: see `load_mut`.
+        Ok(unsafe { &*(bytes.as_ptr() as *const Self) })
+    }
+
+    #[inline(always)]
+    pub fn set_inner(&mut self, authority: Pubkey, bump: u8, seed: &[u8]) {
+        self.header
+            .set_inner(StateKey::Counter, Counter::VERSION, authority, bump, seed);
+        self.set_limits(&Limits::UNBOUNDED);
+        self.delegate_count = [0; 8];
+        self.has_history = 0;
+        self.set_value(0);
+    }
+
+    #[inline(always)]
+    pub fn value(&self) -> u64 {
+        u64::from_le_bytes(self.value)
+    }
+
+    #[inline(always)]
+    pub fn set_value(&mut self, value: u64) {
+        self.value = value.to_le_bytes();
+    }
+
+    #[inline(always)]
+    pub fn limits(&self) -> Limits {
+        Limits {
+            min: u64::from_le_bytes(self.min),
+            max: u64::from_le_bytes(self.max),
+            max_step: u64::from_le_bytes(self.max_step),
         }
-        Ok(unsafe { &*core::mem::transmute::<*const u8, *const Self>(bytes.as_ptr()) })
     }
 
     #[inline(always)]
-    pub fn set_inner(&mut self, authority: Pubkey, bump: u8) {
-        self.key = StateKey::Counter;
-        self.bump = bump;
-        self.authority = authority;
-        self.value = 0;
+    pub fn set_limits(&mut self, limits: &Limits) {
+        self.min = limits.min.to_le_bytes();
+        self.max = limits.max.to_le_bytes();
+        self.max_step = limits.max_step.to_le_bytes();
+    }
+
+    /// Replaces the limits, which must contain the current value.
+    #[inline(always)]
+    pub fn configure(&mut self, limits: &Limits) -> Result<(), ProgramError> {
+        limits.check(self.value())?;
+        self.set_limits(limits);
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn delegate_count(&self) -> u64 {
+        u64::from_le_bytes(self.delegate_count)
+    }
+
+    /// Accounts for a newly approved delegate.
+    #[inline(always)]
+    pub fn add_delegate(&mut self) -> Result<(), ProgramError> {
+        let delegate_count = self
+            .delegate_count()
+            .checked_add(1)
+            .ok_or(CounterError::ArithmeticOverflow)?;
+        self.delegate_count = delegate_count.to_le_bytes();
+        Ok(())
+    }
+
+    /// Accounts for a revoked delegate.
+    #[inline(always)]
+    pub fn remove_delegate(&mut self) {
+        self.delegate_count = self.delegate_count().saturating_sub(1).to_le_bytes();
+    }
+
+    #[inline(always)]
+    pub fn has_history(&self) -> bool {
+        self.has_history != 0
+    }
+
+    #[inline(always)]
+    pub fn set_has_history(&mut self, has_history: bool) {
+        self.has_history = has_history as u8;
+    }
+
+    /// Fails while the counter has delegates. Their accounts are keyed by the
+    /// counter's address, so they would otherwise outlive it and apply to a
+    /// counter later created at the same address.
+    #[inline(always)]
+    pub fn check_no_delegates(&self) -> Result<(), ProgramError> {
+        if self.delegate_count() != 0 {
+            return Err(CounterError::DelegatesOutstanding.into());
+        }
+        Ok(())
     }
 
     #[inline(always)]
     pub fn increase_by(&mut self, amount: u64) -> Result<(), ProgramError> {
-        self.value = self
-            .value
+        if amount > self.limits().max_step {
+            return Err(CounterError::StepTooLarge.into());
+        }
+        let value = self
+            .value()
             .checked_add(amount)
-            .ok_or(ProgramError::ArithmeticOverflow)?;
+            .ok_or(CounterError::ArithmeticOverflow)?;
+        self.limits().check(value)?;
+        self.set_value(value);
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn decrease_by(&mut self, amount: u64) -> Result<(), ProgramError> {
+        if amount > self.limits().max_step {
+            return Err(CounterError::StepTooLarge.into());
+        }
+        let value = self
+            .value()
+            .checked_sub(amount)
+            .ok_or(CounterError::ArithmeticUnderflow)?;
+        self.limits().check(value)?;
+        self.set_value(value);
         Ok(())
     }
 
+    /// Sets the value, keeping it within the configured bounds.
+    #[inline(always)]
+    pub fn set(&mut self, value: u64) -> Result<(), ProgramError> {
+        self.limits().check(value)?;
+        self.set_value(value);
+        Ok(())
+    }
+
+    /// Moves the value back to its lower bound.
+    #[inline(always)]
+    pub fn reset(&mut self) {
+        self.set_value(self.limits().min);
+    }
+
+    #[inline(always)]
+    pub fn compare_and_set(&mut self, expected: u64, new: u64) -> Result<(), ProgramError> {
+        if self.value() != expected {
+            return Err(CounterError::ValueMismatch.into());
+        }
+        self.set(new)
+    }
+
+    /// PDA seeds for a counter. An empty `seed` derives the same address as
+    /// the original `[b"counter", authority]` scheme.
     #[inline(always)]
-    pub fn seeds(authority: &Pubkey) -> [&[u8]; 2] {
-        [b"counter", authority.as_ref()]
+    pub fn seeds<'a>(seed_authority: &'a Pubkey, seed: &'a [u8]) -> [&'a [u8]; 3] {
+        [b"counter", seed_authority.as_ref(), seed]
     }
 
     #[inline(always)]
-    pub fn seeds_with_bump<'a>(authority: &'a Pubkey, bump: &'a [u8]) -> [&'a [u8]; 3] {
-        let seeds = Self::seeds(authority);
-        [seeds[0], seeds[1], bump]
+    pub fn seeds_with_bump<'a>(
+        seed_authority: &'a Pubkey,
+        seed: &'a [u8],
+        bump: &'a [u8],
+    ) -> [&'a [u8]; 4] {
+        let seeds = Self::seeds(seed_authority, seed);
+        [seeds[0], seeds[1], seeds[2], bump]
     }
 
     #[inline(always)]
-    pub fn signer_seeds_with_bump<'a>(authority: &'a Pubkey, bump: &'a [u8]) -> [Seed<'a>; 3] {
-        let seeds = Self::seeds_with_bump(authority, bump);
+    pub fn signer_seeds_with_bump<'a>(
+        seed_authority: &'a Pubkey,
+        seed: &'a [u8],
+        bump: &'a [u8],
+    ) -> [Seed<'a>; 4] {
+        let seeds = Self::seeds_with_bump(seed_authority, seed, bump);
         [
             Seed::from(seeds[0]),
             Seed::from(seeds[1]),
             Seed::from(seeds[2]),
+            Seed::from(seeds[3]),
         ]
     }
 }
+
+impl Deref for Counter {
+    type Target = CounterHeader;
+
+    #[inline(always)]
+    fn deref(&self) -> &CounterHeader {
+        &self.header
+    }
+}
+
+impl DerefMut for Counter {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut CounterHeader {
+        &mut self.header
+    }
+}
+
+impl CounterKind for Counter {
+    const KEY: StateKey = StateKey::Counter;
+
+    const LEN: usize = Counter::LEN;
+
+    type Value = u64;
+
+    type ValueChange = ValueChange;
+
+    #[inline(always)]
+    fn load_mut(bytes: &mut [u8], check_key: bool) -> Result<&mut Self, ProgramError> {
+        Counter::load_mut(bytes, check_key)
+    }
+
+    #[inline(always)]
+    fn set_inner(&mut self, authority: Pubkey, bump: u8, seed: &[u8]) {
+        Counter::set_inner(self, authority, bump, seed)
+    }
+
+    #[inline(always)]
+    fn value(&self) -> u64 {
+        Counter::value(self)
+    }
+
+    #[inline(always)]
+    fn increase_by(&mut self, amount: u64) -> Result<(), ProgramError> {
+        Counter::increase_by(self, amount)
+    }
+
+    #[inline(always)]
+    fn decrease_by(&mut self, amount: u64) -> Result<(), ProgramError> {
+        Counter::decrease_by(self, amount)
+    }
+
+    #[inline(always)]
+    fn reset(&mut self) {
+        Counter::reset(self)
+    }
+
+    #[inline(always)]
+    fn record_history(
+        &self,
+        history: Option<&AccountInfo>,
+        slot: u64,
+        previous: u64,
+    ) -> Result<(), ProgramError> {
+        HistoryAccount::record(history, self, slot, self.value() as i128 - previous as i128)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn len_matches_layout() {
+        assert_eq!(size_of::<Counter>(), Counter::LEN);
+        assert_eq!(align_of::<Counter>(), 1);
+    }
+
+    #[test]
+    fn load_from_unaligned_buffer() {
+        // Offset by one byte so the counter never starts on an aligned address
+        let mut buffer = [0u8; Counter::LEN + 1];
+        let bytes = &mut buffer[1..];
+
+        let counter = Counter::load_mut(bytes, false).unwrap();
+        counter.set_inner([7; 32], 254, b"metrics");
+        counter.increase_by(u64::MAX - 1).unwrap();
+        counter.decrease_by(1).unwrap();
+
+        let counter = Counter::load(bytes).unwrap();
+        assert_eq!(counter.key, StateKey::Counter as u8);
+        assert_eq!(counter.bump, 254);
+        assert_eq!(counter.authority, [7; 32]);
+        assert_eq!(counter.seed(), b"metrics");
+        assert_eq!(counter.value(), u64::MAX - 2);
+        // The value is the trailing little-endian u64
+        assert_eq!(bytes[Counter::LEN - 8..], (u64::MAX - 2).to_le_bytes());
+    }
+
+    #[test]
+    fn load_rejects_wrong_size_and_key() {
+        let mut bytes = [0u8; Counter::LEN];
+        assert_eq!(
+            Counter::load(&bytes).unwrap_err(),
+            CounterError::NotInitialized.into()
+        );
+        assert!(Counter::load_mut(&mut bytes, false).is_ok());
+
+        let mut short = [StateKey::Counter as u8; Counter::LEN - 1];
+        assert_eq!(
+            Counter::load(&short).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+        assert_eq!(
+            Counter::load_mut(&mut short, false).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+    }
+
+    #[test]
+    fn load_rejects_other_versions() {
+        let mut v1 = [0u8; CounterV1::LEN];
+        v1[0] = StateKey::Counter as u8;
+        assert_eq!(
+            Counter::load(&v1).unwrap_err(),
+            CounterError::OutdatedVersion.into()
+        );
+
+        let mut bytes = [0u8; Counter::LEN];
+        Counter::load_mut(&mut bytes, false)
+            .unwrap()
+            .set_inner([7; 32], 254, &[]);
+        bytes[1] = Counter::VERSION + 1;
+        assert_eq!(
+            Counter::load_mut(&mut bytes, true).unwrap_err(),
+            CounterError::UnsupportedVersion.into()
+        );
+    }
+
+    #[test]
+    fn rate_limit_enforces_cooldown_and_window() {
+        let mut bytes = [0u8; Counter::LEN];
+        let counter = Counter::load_mut(&mut bytes, false).unwrap();
+        counter.set_inner([7; 32], 254, &[]);
+        counter.set_rate_limit(&RateLimit {
+            cooldown_slots: 2,
+            cooldown_seconds: 0,
+            window_slots: 10,
+            window_max: 5,
+        });
+
+        counter.rate_limit_increase(3, 100, 1_000).unwrap();
+        counter.record_update(100, 1_000);
+        assert_eq!(
+            counter.rate_limit_increase(1, 101, 1_001).unwrap_err(),
+            CounterError::RateLimited.into()
+        );
+
+        counter.rate_limit_increase(2, 102, 1_002).unwrap();
+        counter.record_update(102, 1_002);
+        assert_eq!(counter.window_total(), 5);
+        assert_eq!(
+            counter.rate_limit_increase(1, 109, 1_009).unwrap_err(),
+            CounterError::RateLimited.into()
+        );
+
+        // A new window starts once `window_slots` have passed
+        counter.rate_limit_increase(5, 110, 1_010).unwrap();
+        assert_eq!(counter.window_start(), 110);
+        assert_eq!(counter.window_total(), 5);
+    }
+
+    #[test]
+    fn limits_are_enforced() {
+        let mut bytes = [0u8; Counter::LEN];
+        let counter = Counter::load_mut(&mut bytes, false).unwrap();
+        counter.set_inner([7; 32], 254, &[]);
+        counter
+            .configure(&Limits {
+                min: 0,
+                max: 10,
+                max_step: 5,
+            })
+            .unwrap();
+
+        assert_eq!(
+            counter.increase_by(6).unwrap_err(),
+            CounterError::StepTooLarge.into()
+        );
+        counter.increase_by(5).unwrap();
+        counter.increase_by(5).unwrap();
+        assert_eq!(
+            counter.increase_by(1).unwrap_err(),
+            CounterError::AboveMaximum.into()
+        );
+        assert_eq!(
+            counter.set(11).unwrap_err(),
+            CounterError::AboveMaximum.into()
+        );
+
+        // Limits that exclude the current value are rejected and left as is
+        let limits = counter.limits();
+        assert_eq!(
+            counter
+                .configure(&Limits {
+                    min: 0,
+                    max: 9,
+                    max_step: 5,
+                })
+                .unwrap_err(),
+            CounterError::AboveMaximum.into()
+        );
+        assert_eq!(counter.limits(), limits);
+        assert_eq!(counter.value(), 10);
+
+        let mut invalid = Limits::UNBOUNDED.to_bytes();
+        invalid[..8].copy_from_slice(&u64::MAX.to_le_bytes());
+        invalid[8..16].copy_from_slice(&1u64.to_le_bytes());
+        assert_eq!(
+            Limits::try_from(&invalid[..]).unwrap_err(),
+            CounterError::InvalidLimits.into()
+        );
+    }
+}
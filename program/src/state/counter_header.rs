@@ -0,0 +1,300 @@
+use core::{
+    mem::{align_of, size_of},
+    ops::DerefMut,
+};
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{Pubkey, MAX_SEED_LEN},
+};
+
+use crate::{
+    error::CounterError,
+    events::EventValue,
+    return_data::KindValueChange,
+    state::{AnyCounter, CounterStatus, RateLimit, StateKey},
+};
+
+/// Leading fields of every counter kind: identity, authority, status, rate
+/// limit and update metadata. `Counter`, `SignedCounter` and `WideCounter`
+/// start with it and dereference to it, so freezing, rate limiting and
+/// update tracking behave the same for all of them.
+///
+/// Like the counters, every field is a byte or byte array so the struct has
+/// an alignment of 1 and no padding.
+#[repr(C)]
+#[derive(Debug)]
+pub struct CounterHeader {
+    /// `StateKey` discriminator.
+    pub key: u8,
+    /// Layout version of the counter kind.
+    pub version: u8,
+    pub bump: u8,
+    /// Number of meaningful bytes in `seed`.
+    pub seed_len: u8,
+    /// `CounterStatus` of the counter.
+    pub status: u8,
+    /// Key currently allowed to mutate the counter.
+    pub authority: Pubkey,
+    /// Authority the PDA was derived from at creation; it never changes.
+    pub seed_authority: Pubkey,
+    /// Key proposed by `authority` that still has to accept the transfer.
+    pub pending_authority: Pubkey,
+    /// User-supplied seed that lets one authority own several counters.
+    pub seed: [u8; MAX_SEED_LEN],
+    cooldown_slots: [u8; 8],
+    cooldown_seconds: [u8; 8],
+    window_slots: [u8; 8],
+    window_max: [u8; 8],
+    last_updated_slot: [u8; 8],
+    last_updated_unix_timestamp: [u8; 8],
+    window_start: [u8; 8],
+    window_total: [u8; 8],
+    update_count: [u8; 8],
+}
+
+const _: () = assert!(size_of::<CounterHeader>() == CounterHeader::LEN);
+const _: () = assert!(align_of::<CounterHeader>() == 1);
+
+impl CounterHeader {
+    pub const LEN: usize = size_of::<u8>()
+        + size_of::<u8>()
+        + size_of::<u8>()
+        + size_of::<u8>()
+        + size_of::<u8>()
+        + size_of::<Pubkey>()
+        + size_of::<Pubkey>()
+        + size_of::<Pubkey>()
+        + MAX_SEED_LEN
+        + RateLimit::LEN
+        + size_of::<u64>()
+        + size_of::<i64>()
+        + size_of::<u64>()
+        + size_of::<u64>()
+        + size_of::<u64>();
+
+    /// Loads the header of a counter of any kind. Fails like
+    /// `AnyCounter::load` if `bytes` hold no current counter.
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        AnyCounter::load(bytes)?;
+        // SAFETY: every counter kind starts with a `CounterHeader`, which has
+        // alignment 1 and no padding, and `AnyCounter::load` checked that
+        // `bytes` hold a whole counter.
+        Ok(unsafe { &mut *(bytes.as_mut_ptr() as *mut Self) })
+    }
+
+    #[inline(always)]
+    pub fn set_inner(
+        &mut self,
+        key: StateKey,
+        version: u8,
+        authority: Pubkey,
+        bump: u8,
+        seed: &[u8],
+    ) {
+        self.key = key as u8;
+        self.version = version;
+        self.bump = bump;
+        self.authority = authority;
+        self.seed_authority = authority;
+        self.pending_authority = Pubkey::default();
+        self.seed = [0; MAX_SEED_LEN];
+        self.seed[..seed.len()].copy_from_slice(seed);
+        self.seed_len = seed.len() as u8;
+        self.status = CounterStatus::Active as u8;
+        self.set_rate_limit(&RateLimit::DISABLED);
+        self.last_updated_slot = [0; 8];
+        self.last_updated_unix_timestamp = [0; 8];
+        self.window_start = [0; 8];
+        self.window_total = [0; 8];
+        self.update_count = [0; 8];
+    }
+
+    /// The user-supplied part of the PDA seeds.
+    #[inline(always)]
+    pub fn seed(&self) -> &[u8] {
+        &self.seed[..self.seed_len as usize]
+    }
+
+    #[inline(always)]
+    pub fn is_frozen(&self) -> bool {
+        self.status == CounterStatus::Frozen as u8
+    }
+
+    #[inline(always)]
+    pub fn set_status(&mut self, status: CounterStatus) {
+        self.status = status as u8;
+    }
+
+    /// Fails if the counter is frozen. Every mutating instruction calls this
+    /// before touching the counter.
+    #[inline(always)]
+    pub fn check_not_frozen(&self) -> Result<(), ProgramError> {
+        if self.is_frozen() {
+            return Err(CounterError::Frozen.into());
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn rate_limit(&self) -> RateLimit {
+        RateLimit {
+            cooldown_slots: u64::from_le_bytes(self.cooldown_slots),
+            cooldown_seconds: u64::from_le_bytes(self.cooldown_seconds),
+            window_slots: u64::from_le_bytes(self.window_slots),
+            window_max: u64::from_le_bytes(self.window_max),
+        }
+    }
+
+    #[inline(always)]
+    pub fn set_rate_limit(&mut self, rate_limit: &RateLimit) {
+        self.cooldown_slots = rate_limit.cooldown_slots.to_le_bytes();
+        self.cooldown_seconds = rate_limit.cooldown_seconds.to_le_bytes();
+        self.window_slots = rate_limit.window_slots.to_le_bytes();
+        self.window_max = rate_limit.window_max.to_le_bytes();
+    }
+
+    #[inline(always)]
+    pub fn last_updated_slot(&self) -> u64 {
+        u64::from_le_bytes(self.last_updated_slot)
+    }
+
+    #[inline(always)]
+    pub fn last_updated_unix_timestamp(&self) -> i64 {
+        i64::from_le_bytes(self.last_updated_unix_timestamp)
+    }
+
+    /// Number of value changes since the counter was created.
+    #[inline(always)]
+    pub fn update_count(&self) -> u64 {
+        u64::from_le_bytes(self.update_count)
+    }
+
+    /// Stamps the counter with the given clock without counting an update.
+    #[inline(always)]
+    pub fn touch(&mut self, slot: u64, unix_timestamp: i64) {
+        self.last_updated_slot = slot.to_le_bytes();
+        self.last_updated_unix_timestamp = unix_timestamp.to_le_bytes();
+    }
+
+    /// Records a value change at the given clock. Every mutating
+    /// instruction calls this.
+    #[inline(always)]
+    pub fn record_update(&mut self, slot: u64, unix_timestamp: i64) {
+        self.touch(slot, unix_timestamp);
+        self.update_count = self.update_count().saturating_add(1).to_le_bytes();
+    }
+
+    /// First slot of the current rate limit window.
+    #[inline(always)]
+    pub fn window_start(&self) -> u64 {
+        u64::from_le_bytes(self.window_start)
+    }
+
+    /// Amount added since `window_start`.
+    #[inline(always)]
+    pub fn window_total(&self) -> u64 {
+        u64::from_le_bytes(self.window_total)
+    }
+
+    /// Checks an increase of `amount` at the given clock against the rate
+    /// limit and accounts for it in the current window. A counter that was
+    /// never updated has no cooldown to wait for.
+    #[inline(always)]
+    pub fn rate_limit_increase(
+        &mut self,
+        amount: u64,
+        slot: u64,
+        unix_timestamp: i64,
+    ) -> Result<(), ProgramError> {
+        let rate_limit = self.rate_limit();
+
+        if self.update_count() != 0 {
+            let elapsed_slots = slot.saturating_sub(self.last_updated_slot());
+            let elapsed_seconds = unix_timestamp
+                .saturating_sub(self.last_updated_unix_timestamp())
+                .max(0) as u64;
+            if elapsed_slots < rate_limit.cooldown_slots
+                || elapsed_seconds < rate_limit.cooldown_seconds
+            {
+                return Err(CounterError::RateLimited.into());
+            }
+        }
+
+        if rate_limit.window_slots != 0 {
+            let mut window_total = self.window_total();
+            if slot.saturating_sub(self.window_start()) >= rate_limit.window_slots {
+                self.window_start = slot.to_le_bytes();
+                window_total = 0;
+            }
+            let window_total = window_total
+                .checked_add(amount)
+                .filter(|total| *total <= rate_limit.window_max)
+                .ok_or(CounterError::RateLimited)?;
+            self.window_total = window_total.to_le_bytes();
+        }
+
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn propose_authority(&mut self, new_authority: Pubkey) {
+        self.pending_authority = new_authority;
+    }
+
+    #[inline(always)]
+    pub fn accept_authority(&mut self, new_authority: &Pubkey) -> Result<(), ProgramError> {
+        if self.pending_authority == Pubkey::default() || &self.pending_authority != new_authority {
+            return Err(CounterError::NoPendingAuthority.into());
+        }
+        self.authority = self.pending_authority;
+        self.pending_authority = Pubkey::default();
+        Ok(())
+    }
+}
+
+/// A kind of counter account, starting with a `CounterHeader`. `CreateKind`
+/// creates the kinds without limits, and `Increase`, `Decrease` and `Reset`
+/// change the value of any kind the same way.
+pub trait CounterKind: DerefMut<Target = CounterHeader> {
+    /// `StateKey` of the kind.
+    const KEY: StateKey;
+
+    /// Size of the account.
+    const LEN: usize;
+
+    type Value: EventValue;
+
+    /// Published through return data by the instructions that change the
+    /// value.
+    type ValueChange: KindValueChange<Value = Self::Value>;
+
+    fn load_mut(bytes: &mut [u8], check_key: bool) -> Result<&mut Self, ProgramError>;
+
+    /// Initializes a new counter of this kind with a zero value.
+    fn set_inner(&mut self, authority: Pubkey, bump: u8, seed: &[u8]);
+
+    fn value(&self) -> Self::Value;
+
+    fn increase_by(&mut self, amount: u64) -> Result<(), ProgramError>;
+
+    fn decrease_by(&mut self, amount: u64) -> Result<(), ProgramError>;
+
+    /// Moves the value back to its lower bound.
+    fn reset(&mut self);
+
+    /// Records the change of the value from `previous` in the counter's
+    /// `History`. Only `Counter`s have one, so the other kinds ignore
+    /// `history`.
+    #[inline(always)]
+    fn record_history(
+        &self,
+        history: Option<&AccountInfo>,
+        slot: u64,
+        previous: Self::Value,
+    ) -> Result<(), ProgramError> {
+        let _ = (history, slot, previous);
+        Ok(())
+    }
+}
@@ -0,0 +1,70 @@
+use core::mem::{align_of, size_of};
+use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{error::CounterError, state::StateKey};
+
+/// Read-only view of version 1 counters, the original 42-byte accounts, kept
+/// so they can be migrated to the current `Counter` layout. They have no
+/// `version` byte and are recognised by their length.
+///
+/// The original `#[repr(C)]` struct placed its `u64` value at offset 40,
+/// after six bytes of padding, while the accounts were only `LEN` (42) bytes
+/// long. Only the two low bytes of the value were ever persisted, so that is
+/// all that can be recovered.
+#[repr(C)]
+#[derive(Debug)]
+pub struct CounterV1 {
+    /// `StateKey` discriminator.
+    pub key: u8,
+    pub bump: u8,
+    pub authority: Pubkey,
+    _padding: [u8; 6],
+    value: [u8; 2],
+}
+
+const _: () = assert!(size_of::<CounterV1>() == CounterV1::LEN);
+const _: () = assert!(align_of::<CounterV1>() == 1);
+
+impl CounterV1 {
+    pub const VERSION: u8 = 1;
+
+    pub const LEN: usize = 42;
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != CounterV1::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if bytes[0] != StateKey::Counter as u8 {
+            return Err(CounterError::NotInitialized.into());
+        }
+        // SAFETY: `CounterV1` has alignment 1, no padding and is exactly
+        // `CounterV1::LEN` bytes, so any byte slice of that length is
+        // valid.
+        Ok(unsafe { &*(bytes.as_ptr() as *const Self) })
+    }
+
+    #[inline(always)]
+    pub fn value(&self) -> u64 {
+        u16::from_le_bytes(self.value) as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_recovers_persisted_value_bytes() {
+        let mut bytes = [0u8; CounterV1::LEN];
+        bytes[0] = StateKey::Counter as u8;
+        bytes[1] = 253;
+        bytes[2..34].copy_from_slice(&[9; 32]);
+        bytes[40..42].copy_from_slice(&0x1234u16.to_le_bytes());
+
+        let counter = CounterV1::load(&bytes).unwrap();
+        assert_eq!(counter.bump, 253);
+        assert_eq!(counter.authority, [9; 32]);
+        assert_eq!(counter.value(), 0x1234);
+    }
+}
@@ -0,0 +1,174 @@
+use core::mem::{align_of, size_of};
+use pinocchio::{instruction::Seed, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{error::CounterError, state::StateKey};
+
+/// Zero-copy view of a delegate account, which lets `delegate` increase
+/// `counter` by up to `allowance` in total until `expiry_slot`.
+///
+/// Like `Counter`, every field is a byte or byte array so the struct has an
+/// alignment of 1 and no padding.
+#[repr(C)]
+#[derive(Debug)]
+pub struct Delegate {
+    /// `StateKey` discriminator.
+    pub key: u8,
+    pub bump: u8,
+    pub counter: Pubkey,
+    pub delegate: Pubkey,
+    allowance: [u8; 8],
+    expiry_slot: [u8; 8],
+}
+
+const _: () = assert!(size_of::<Delegate>() == Delegate::LEN);
+const _: () = assert!(align_of::<Delegate>() == 1);
+
+impl Delegate {
+    pub const LEN: usize = size_of::<u8>()
+        + size_of::<u8>()
+        + size_of::<Pubkey>()
+        + size_of::<Pubkey>()
+        + size_of::<u64>()
+        + size_of::<u64>();
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8], check_key: bool) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != Delegate::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if check_key && bytes[0] != StateKey::Delegate as u8 {
+            return Err(CounterError::NotInitialized.into());
+        }
+        // SAFETY: `Delegate` has alignment 1, no padding and is exactly
+        // `Delegate::LEN` bytes, so any byte slice of that length is valid.
+        Ok(unsafe { &mut *(bytes.as_mut_ptr() as *mut Self) })
+    }
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != Delegate::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if bytes[0] != StateKey::Delegate as u8 {
+            return Err(CounterError::NotInitialized.into());
+        }
+        // SAFETY: see `load_mut`.
+        Ok(unsafe { &*(bytes.as_ptr() as *const Self) })
+    }
+
+    #[inline(always)]
+    pub fn set_inner(
+        &mut self,
+        counter: Pubkey,
+        delegate: Pubkey,
+        bump: u8,
+        allowance: u64,
+        expiry_slot: Option<u64>,
+    ) {
+        self.key = StateKey::Delegate as u8;
+        self.bump = bump;
+        self.counter = counter;
+        self.delegate = delegate;
+        self.set_allowance(allowance);
+        self.set_expiry_slot(expiry_slot);
+    }
+
+    /// Total amount the delegate may still add to the counter.
+    #[inline(always)]
+    pub fn allowance(&self) -> u64 {
+        u64::from_le_bytes(self.allowance)
+    }
+
+    #[inline(always)]
+    pub fn set_allowance(&mut self, allowance: u64) {
+        self.allowance = allowance.to_le_bytes();
+    }
+
+    /// Last slot in which the delegate may act; `None` never expires.
+    #[inline(always)]
+    pub fn expiry_slot(&self) -> Option<u64> {
+        match u64::from_le_bytes(self.expiry_slot) {
+            0 => None,
+            slot => Some(slot),
+        }
+    }
+
+    #[inline(always)]
+    pub fn set_expiry_slot(&mut self, expiry_slot: Option<u64>) {
+        self.expiry_slot = expiry_slot.unwrap_or(0).to_le_bytes();
+    }
+
+    /// Draws `amount` from the allowance at `slot`.
+    #[inline(always)]
+    pub fn draw(&mut self, amount: u64, slot: u64) -> Result<(), ProgramError> {
+        if self
+            .expiry_slot()
+            .is_some_and(|expiry_slot| slot > expiry_slot)
+        {
+            return Err(CounterError::DelegateExpired.into());
+        }
+        let allowance = self
+            .allowance()
+            .checked_sub(amount)
+            .ok_or(CounterError::AllowanceExceeded)?;
+        self.set_allowance(allowance);
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn seeds<'a>(counter: &'a Pubkey, delegate: &'a Pubkey) -> [&'a [u8]; 3] {
+        [b"delegate", counter.as_ref(), delegate.as_ref()]
+    }
+
+    #[inline(always)]
+    pub fn seeds_with_bump<'a>(
+        counter: &'a Pubkey,
+        delegate: &'a Pubkey,
+        bump: &'a [u8],
+    ) -> [&'a [u8]; 4] {
+        let seeds = Self::seeds(counter, delegate);
+        [seeds[0], seeds[1], seeds[2], bump]
+    }
+
+    #[inline(always)]
+    pub fn signer_seeds_with_bump<'a>(
+        counter: &'a Pubkey,
+        delegate: &'a Pubkey,
+        bump: &'a [u8],
+    ) -> [Seed<'a>; 4] {
+        let seeds = Self::seeds_with_bump(counter, delegate, bump);
+        [
+            Seed::from(seeds[0]),
+            Seed::from(seeds[1]),
+            Seed::from(seeds[2]),
+            Seed::from(seeds[3]),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn draw_respects_allowance_and_expiry() {
+        let mut bytes = [0u8; Delegate::LEN];
+        let delegate = Delegate::load_mut(&mut bytes, false).unwrap();
+        delegate.set_inner([1; 32], [2; 32], 255, 10, Some(100));
+
+        delegate.draw(4, 100).unwrap();
+        assert_eq!(delegate.allowance(), 6);
+        assert_eq!(
+            delegate.draw(7, 100).unwrap_err(),
+            CounterError::AllowanceExceeded.into()
+        );
+        assert_eq!(
+            delegate.draw(1, 101).unwrap_err(),
+            CounterError::DelegateExpired.into()
+        );
+
+        delegate.set_expiry_slot(None);
+        delegate.draw(6, u64::MAX).unwrap();
+        assert_eq!(Delegate::load(&bytes).unwrap().allowance(), 0);
+    }
+}
@@ -0,0 +1,188 @@
+use core::mem::{align_of, size_of};
+use pinocchio::{instruction::Seed, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{error::CounterError, state::StateKey};
+
+/// One value change recorded in a `History`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HistoryEntry {
+    slot: [u8; 8],
+    delta: [u8; 16],
+    new_value: [u8; 8],
+}
+
+impl HistoryEntry {
+    pub const LEN: usize = size_of::<u64>() + size_of::<i128>() + size_of::<u64>();
+
+    #[inline(always)]
+    pub fn new(slot: u64, delta: i128, new_value: u64) -> Self {
+        Self {
+            slot: slot.to_le_bytes(),
+            delta: delta.to_le_bytes(),
+            new_value: new_value.to_le_bytes(),
+        }
+    }
+
+    #[inline(always)]
+    pub fn slot(&self) -> u64 {
+        u64::from_le_bytes(self.slot)
+    }
+
+    /// Signed change of the value, wide enough for any `u64` amount.
+    #[inline(always)]
+    pub fn delta(&self) -> i128 {
+        i128::from_le_bytes(self.delta)
+    }
+
+    /// The value after the change, encoded like `CounterEvent` values.
+    #[inline(always)]
+    pub fn new_value(&self) -> u64 {
+        u64::from_le_bytes(self.new_value)
+    }
+}
+
+/// Zero-copy view of a counter's history: a ring buffer of its last
+/// `History::CAPACITY` value changes, kept at `[b"history", counter]`.
+///
+/// A history is optional. Once a counter has one, every instruction that
+/// changes its value has to be passed the history after its other accounts,
+/// and records the change.
+#[repr(C)]
+#[derive(Debug)]
+pub struct History {
+    /// `StateKey` discriminator.
+    pub key: u8,
+    pub bump: u8,
+    /// Index the next entry is written to.
+    next: u8,
+    /// Number of recorded entries, up to `CAPACITY`.
+    len: u8,
+    pub counter: Pubkey,
+    entries: [HistoryEntry; History::CAPACITY],
+}
+
+const _: () = assert!(size_of::<HistoryEntry>() == HistoryEntry::LEN);
+const _: () = assert!(size_of::<History>() == History::LEN);
+const _: () = assert!(align_of::<History>() == 1);
+
+impl History {
+    pub const CAPACITY: usize = 32;
+
+    pub const LEN: usize = size_of::<u8>()
+        + size_of::<u8>()
+        + size_of::<u8>()
+        + size_of::<u8>()
+        + size_of::<Pubkey>()
+        + Self::CAPACITY * HistoryEntry::LEN;
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8], check_key: bool) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != History::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if check_key && bytes[0] != StateKey::History as u8 {
+            return Err(CounterError::NotInitialized.into());
+        }
+        // SAFETY: `History` has alignment 1, no padding and is exactly
+        // `History::LEN` bytes, so any byte slice of that length is valid.
+        Ok(unsafe { &mut *(bytes.as_mut_ptr() as *mut Self) })
+    }
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != History::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if bytes[0] != StateKey::History as u8 {
+            return Err(CounterError::NotInitialized.into());
+        }
+        // SAFETY: see `load_mut`.
+        Ok(unsafe { &*(bytes.as_ptr() as *const Self) })
+    }
+
+    #[inline(always)]
+    pub fn set_inner(&mut self, counter: Pubkey, bump: u8) {
+        self.key = StateKey::History as u8;
+        self.bump = bump;
+        self.next = 0;
+        self.len = 0;
+        self.counter = counter;
+        self.entries = [HistoryEntry::default(); Self::CAPACITY];
+    }
+
+    /// Records a change, overwriting the oldest entry once full.
+    #[inline(always)]
+    pub fn append(&mut self, entry: HistoryEntry) {
+        self.entries[self.next as usize] = entry;
+        self.next = ((self.next as usize + 1) % Self::CAPACITY) as u8;
+        self.len = (self.len as usize + 1).min(Self::CAPACITY) as u8;
+    }
+
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Recorded entries, oldest first.
+    #[inline(always)]
+    pub fn entries(&self) -> impl Iterator<Item = &HistoryEntry> {
+        let start = (self.next as usize + Self::CAPACITY - self.len()) % Self::CAPACITY;
+        (0..self.len()).map(move |offset| &self.entries[(start + offset) % Self::CAPACITY])
+    }
+
+    #[inline(always)]
+    pub fn seeds(counter: &Pubkey) -> [&[u8]; 2] {
+        [b"history", counter.as_ref()]
+    }
+
+    #[inline(always)]
+    pub fn seeds_with_bump<'a>(counter: &'a Pubkey, bump: &'a [u8]) -> [&'a [u8]; 3] {
+        let seeds = Self::seeds(counter);
+        [seeds[0], seeds[1], bump]
+    }
+
+    #[inline(always)]
+    pub fn signer_seeds_with_bump<'a>(counter: &'a Pubkey, bump: &'a [u8]) -> [Seed<'a>; 3] {
+        let seeds = Self::seeds_with_bump(counter, bump);
+        [
+            Seed::from(seeds[0]),
+            Seed::from(seeds[1]),
+            Seed::from(seeds[2]),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn append_wraps_around_keeping_the_latest_entries() {
+        let mut bytes = [0u8; History::LEN];
+        let history = History::load_mut(&mut bytes, false).unwrap();
+        history.set_inner([7; 32], 254);
+        assert!(history.is_empty());
+
+        history.append(HistoryEntry::new(1, -1, 4));
+        history.append(HistoryEntry::new(2, 3, 7));
+        assert_eq!(
+            history.entries().copied().collect::<Vec<_>>(),
+            [HistoryEntry::new(1, -1, 4), HistoryEntry::new(2, 3, 7)]
+        );
+
+        for slot in 3..History::CAPACITY as u64 + 5 {
+            history.append(HistoryEntry::new(slot, 1, slot));
+        }
+        let history = History::load(&bytes).unwrap();
+        assert_eq!(history.len(), History::CAPACITY);
+        let slots: Vec<u64> = history.entries().map(HistoryEntry::slot).collect();
+        assert_eq!(slots, (5..History::CAPACITY as u64 + 5).collect::<Vec<_>>());
+        assert_eq!(history.entries().last().unwrap().delta(), 1);
+    }
+}
@@ -1,5 +1,27 @@
+pub mod any_counter;
+pub mod config;
 pub mod counter;
+pub mod counter_header;
+pub mod counter_v1;
+pub mod delegate;
+pub mod history;
+pub mod multisig;
+pub mod signed_counter;
+pub mod tally;
 pub mod utils;
+pub mod versioned_counter;
+pub mod wide_counter;
 
+pub use any_counter::*;
+pub use config::*;
 pub use counter::*;
+pub use counter_header::*;
+pub use counter_v1::*;
+pub use delegate::*;
+pub use history::*;
+pub use multisig::*;
+pub use signed_counter::*;
+pub use tally::*;
 pub use utils::*;
+pub use versioned_counter::*;
+pub use wide_counter::*;
@@ -0,0 +1,181 @@
+use core::mem::{align_of, size_of};
+use pinocchio::{instruction::Seed, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{error::CounterError, state::StateKey};
+
+/// Zero-copy view of an m-of-n signer set. Storing its address as a
+/// counter's authority makes every authority instruction require `m` of its
+/// `n` members to sign instead of a single key.
+#[repr(C)]
+#[derive(Debug)]
+pub struct Multisig {
+    /// `StateKey` discriminator.
+    pub key: u8,
+    pub bump: u8,
+    /// Number of members that must sign.
+    pub m: u8,
+    /// Number of meaningful entries in `signers`.
+    pub n: u8,
+    pub signers: [Pubkey; Multisig::MAX_SIGNERS],
+}
+
+const _: () = assert!(size_of::<Multisig>() == Multisig::LEN);
+const _: () = assert!(align_of::<Multisig>() == 1);
+
+impl Multisig {
+    pub const MAX_SIGNERS: usize = 11;
+
+    pub const LEN: usize = size_of::<u8>()
+        + size_of::<u8>()
+        + size_of::<u8>()
+        + size_of::<u8>()
+        + Self::MAX_SIGNERS * size_of::<Pubkey>();
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8], check_key: bool) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != Multisig::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if check_key && bytes[0] != StateKey::Multisig as u8 {
+            return Err(CounterError::NotInitialized.into());
+        }
+        // SAFETY: `Multisig` has alignment 1, no padding and is exactly
+        // `Multisig::LEN` bytes, so any byte slice of that length is valid.
+        Ok(unsafe { &mut *(bytes.as_mut_ptr() as *mut Self) })
+    }
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != Multisig::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if bytes[0] != StateKey::Multisig as u8 {
+            return Err(CounterError::NotInitialized.into());
+        }
+        // SAFETY: see `load_mut`.
+        Ok(unsafe { &*(bytes.as_ptr() as *const Self) })
+    }
+
+    /// Fails unless `1 <= m <= signers.len() <= MAX_SIGNERS` and the
+    /// signers are distinct.
+    #[inline(always)]
+    pub fn set_inner<'a>(
+        &mut self,
+        bump: u8,
+        m: u8,
+        signers: impl ExactSizeIterator<Item = &'a Pubkey>,
+    ) -> Result<(), ProgramError> {
+        let n = signers.len();
+        if m == 0 || m as usize > n || n > Self::MAX_SIGNERS {
+            return Err(CounterError::InvalidMultisig.into());
+        }
+
+        self.key = StateKey::Multisig as u8;
+        self.bump = bump;
+        self.m = m;
+        self.n = n as u8;
+        self.signers = [Pubkey::default(); Self::MAX_SIGNERS];
+        for (index, signer) in signers.enumerate() {
+            if self.signers[..index].contains(signer) {
+                return Err(CounterError::InvalidMultisig.into());
+            }
+            self.signers[index] = *signer;
+        }
+        Ok(())
+    }
+
+    /// The `n` members of the set.
+    #[inline(always)]
+    pub fn signers(&self) -> &[Pubkey] {
+        &self.signers[..self.n as usize]
+    }
+
+    /// Fails unless at least `m` distinct members are among `signed`.
+    #[inline(always)]
+    pub fn check_signed<'a>(
+        &self,
+        signed: impl Iterator<Item = &'a Pubkey> + Clone,
+    ) -> Result<(), ProgramError> {
+        let count = self
+            .signers()
+            .iter()
+            .filter(|member| signed.clone().any(|key| key == *member))
+            .count();
+        if count < self.m as usize {
+            return Err(CounterError::NotEnoughSigners.into());
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn seeds<'a>(creator: &'a Pubkey, seed: &'a [u8]) -> [&'a [u8]; 3] {
+        [b"multisig", creator.as_ref(), seed]
+    }
+
+    #[inline(always)]
+    pub fn seeds_with_bump<'a>(
+        creator: &'a Pubkey,
+        seed: &'a [u8],
+        bump: &'a [u8],
+    ) -> [&'a [u8]; 4] {
+        let seeds = Self::seeds(creator, seed);
+        [seeds[0], seeds[1], seeds[2], bump]
+    }
+
+    #[inline(always)]
+    pub fn signer_seeds_with_bump<'a>(
+        creator: &'a Pubkey,
+        seed: &'a [u8],
+        bump: &'a [u8],
+    ) -> [Seed<'a>; 4] {
+        let seeds = Self::seeds_with_bump(creator, seed, bump);
+        [
+            Seed::from(seeds[0]),
+            Seed::from(seeds[1]),
+            Seed::from(seeds[2]),
+            Seed::from(seeds[3]),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_signed_counts_distinct_members() {
+        let mut bytes = [0u8; Multisig::LEN];
+        let multisig = Multisig::load_mut(&mut bytes, false).unwrap();
+        multisig
+            .set_inner(255, 2, [[1; 32], [2; 32], [3; 32]].iter())
+            .unwrap();
+
+        assert_eq!(multisig.signers(), &[[1; 32], [2; 32], [3; 32]]);
+        multisig.check_signed([[1; 32], [3; 32]].iter()).unwrap();
+        // A member listed twice only counts once, outsiders never count
+        assert_eq!(
+            multisig
+                .check_signed([[1; 32], [1; 32], [9; 32]].iter())
+                .unwrap_err(),
+            CounterError::NotEnoughSigners.into()
+        );
+    }
+
+    #[test]
+    fn set_inner_rejects_invalid_sets() {
+        let mut bytes = [0u8; Multisig::LEN];
+        let multisig = Multisig::load_mut(&mut bytes, false).unwrap();
+
+        for (m, signers) in [
+            (0, &[[1; 32]][..]),
+            (2, &[[1; 32]][..]),
+            (1, &[[1; 32], [1; 32]][..]),
+            (1, &[[1; 32]; Multisig::MAX_SIGNERS + 1][..]),
+        ] {
+            assert_eq!(
+                multisig.set_inner(255, m, signers.iter()).unwrap_err(),
+                CounterError::InvalidMultisig.into()
+            );
+        }
+    }
+}
@@ -0,0 +1,215 @@
+use core::{
+    mem::{align_of, size_of},
+    ops::{Deref, DerefMut},
+};
+use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{
+    error::CounterError,
+    return_data::SignedValueChange,
+    state::{CounterHeader, CounterKind, StateKey},
+};
+
+/// Zero-copy view of a counter holding an `i64`, which may go below zero.
+///
+/// Signed counters share the `Counter` PDA seeds and `CounterHeader` but
+/// have no limits or delegates. Like `Counter`, every field is a byte or
+/// byte array so the struct has an alignment of 1 and no padding.
+#[repr(C)]
+#[derive(Debug)]
+pub struct SignedCounter {
+    pub header: CounterHeader,
+    value: [u8; 8],
+}
+
+const _: () = assert!(size_of::<SignedCounter>() == SignedCounter::LEN);
+const _: () = assert!(align_of::<SignedCounter>() == 1);
+
+impl SignedCounter {
+    pub const VERSION: u8 = 1;
+
+    pub const LEN: usize = CounterHeader::LEN + size_of::<i64>();
+
+    /// Fails unless `bytes` holds a counter of this kind and version. No
+    /// older version exists yet, so there is nothing to migrate from.
+    #[inline(always)]
+    fn check_layout(bytes: &[u8]) -> Result<(), ProgramError> {
+        if bytes.len() != SignedCounter::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if bytes[0] != StateKey::SignedCounter as u8 {
+            return Err(CounterError::NotInitialized.into());
+        }
+        if bytes[1] < SignedCounter::VERSION {
+            return Err(CounterError::OutdatedVersion.into());
+        }
+        if bytes[1] != SignedCounter::VERSION {
+            return Err(CounterError::UnsupportedVersion.into());
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8], check_key: bool) -> Result<&mut Self, ProgramError> {
+        if check_key {
+            Self::check_layout(bytes)?;
+        } else if bytes.len() != SignedCounter::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        // SAFETY: `SignedCounter` has alignment 1, no padding and is exactly
+        // `SignedCounter::LEN` bytes, so any byte slice of that length is valid.
+        Ok(unsafe { &mut *(bytes.as_mut_ptr() as *mut Self) })
+    }
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        Self::check_layout(bytes)?;
+        // SAFETY: see `load_mut`.
+        Ok(unsafe { &*(bytes.as_ptr() as *const Self) })
+    }
+
+    #[inline(always)]
+    pub fn set_inner(&mut self, authority: Pubkey, bump: u8, seed: &[u8]) {
+        self.header.set_inner(
+            StateKey::SignedCounter,
+            SignedCounter::VERSION,
+            authority,
+            bump,
+            seed,
+        );
+        self.set_value(0);
+    }
+
+    #[inline(always)]
+    pub fn value(&self) -> i64 {
+        i64::from_le_bytes(self.value)
+    }
+
+    #[inline(always)]
+    pub fn set_value(&mut self, value: i64) {
+        self.value = value.to_le_bytes();
+    }
+
+    #[inline(always)]
+    pub fn increase_by(&mut self, amount: u64) -> Result<(), ProgramError> {
+        let value = self
+            .value()
+            .checked_add_unsigned(amount)
+            .ok_or(CounterError::ArithmeticOverflow)?;
+        self.set_value(value);
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn decrease_by(&mut self, amount: u64) -> Result<(), ProgramError> {
+        let value = self
+            .value()
+            .checked_sub_unsigned(amount)
+            .ok_or(CounterError::ArithmeticUnderflow)?;
+        self.set_value(value);
+        Ok(())
+    }
+}
+
+impl Deref for SignedCounter {
+    type Target = CounterHeader;
+
+    #[inline(always)]
+    fn deref(&self) -> &CounterHeader {
+        &self.header
+    }
+}
+
+impl DerefMut for SignedCounter {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut CounterHeader {
+        &mut self.header
+    }
+}
+
+impl CounterKind for SignedCounter {
+    const KEY: StateKey = StateKey::SignedCounter;
+
+    const LEN: usize = SignedCounter::LEN;
+
+    type Value = i64;
+
+    type ValueChange = SignedValueChange;
+
+    #[inline(always)]
+    fn load_mut(bytes: &mut [u8], check_key: bool) -> Result<&mut Self, ProgramError> {
+        SignedCounter::load_mut(bytes, check_key)
+    }
+
+    #[inline(always)]
+    fn set_inner(&mut self, authority: Pubkey, bump: u8, seed: &[u8]) {
+        SignedCounter::set_inner(self, authority, bump, seed)
+    }
+
+    #[inline(always)]
+    fn value(&self) -> i64 {
+        SignedCounter::value(self)
+    }
+
+    #[inline(always)]
+    fn increase_by(&mut self, amount: u64) -> Result<(), ProgramError> {
+        SignedCounter::increase_by(self, amount)
+    }
+
+    #[inline(always)]
+    fn decrease_by(&mut self, amount: u64) -> Result<(), ProgramError> {
+        SignedCounter::decrease_by(self, amount)
+    }
+
+    /// Kinds without limits reset to zero.
+    #[inline(always)]
+    fn reset(&mut self) {
+        self.set_value(0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn value_goes_below_zero_within_i64() {
+        let mut bytes = [0u8; SignedCounter::LEN];
+        let counter = SignedCounter::load_mut(&mut bytes, false).unwrap();
+        counter.set_inner([7; 32], 254, b"balance");
+
+        counter.decrease_by(5).unwrap();
+        assert_eq!(counter.value(), -5);
+        counter.increase_by(i64::MAX as u64 + 5).unwrap();
+        assert_eq!(counter.value(), i64::MAX);
+        assert_eq!(
+            counter.increase_by(1).unwrap_err(),
+            CounterError::ArithmeticOverflow.into()
+        );
+
+        counter.set_value(i64::MIN + 1);
+        assert_eq!(
+            counter.decrease_by(2).unwrap_err(),
+            CounterError::ArithmeticUnderflow.into()
+        );
+        assert_eq!(counter.value(), i64::MIN + 1);
+
+        let counter = SignedCounter::load(&bytes).unwrap();
+        assert_eq!(counter.seed(), b"balance");
+        assert_eq!(
+            SignedCounter::load(&bytes[1..]).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+
+        bytes[1] = SignedCounter::VERSION + 1;
+        assert_eq!(
+            SignedCounter::load(&bytes).unwrap_err(),
+            CounterError::UnsupportedVersion.into()
+        );
+        bytes[1] = 0;
+        assert_eq!(
+            SignedCounter::load_mut(&mut bytes, true).unwrap_err(),
+            CounterError::OutdatedVersion.into()
+        );
+    }
+}
@@ -0,0 +1,249 @@
+use core::mem::{align_of, size_of};
+use pinocchio::{
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::{Pubkey, MAX_SEED_LEN},
+};
+
+use crate::{error::CounterError, state::StateKey};
+
+/// A little-endian `u64` slot of a `Tally`.
+pub type TallySlot = [u8; 8];
+
+/// Zero-copy header of a tally account, which holds `slot_count` `u64`
+/// slots in a single PDA instead of one counter per slot.
+///
+/// The header is followed by the slots, so the account is
+/// `Tally::space(slot_count)` bytes. Like `Counter`, every field is a byte
+/// or byte array so both the header and the slots have an alignment of 1.
+#[repr(C)]
+#[derive(Debug)]
+pub struct Tally {
+    /// `StateKey` discriminator.
+    pub key: u8,
+    pub bump: u8,
+    /// Number of meaningful bytes in `seed`.
+    pub seed_len: u8,
+    slot_count: [u8; 2],
+    pub authority: Pubkey,
+    /// User-supplied seed that lets one authority own several tallies.
+    pub seed: [u8; MAX_SEED_LEN],
+}
+
+const _: () = assert!(size_of::<Tally>() == Tally::LEN);
+const _: () = assert!(align_of::<Tally>() == 1);
+const _: () = assert!(align_of::<TallySlot>() == 1);
+
+impl Tally {
+    /// Size of the header.
+    pub const LEN: usize = size_of::<u8>()
+        + size_of::<u8>()
+        + size_of::<u8>()
+        + size_of::<u16>()
+        + size_of::<Pubkey>()
+        + MAX_SEED_LEN;
+
+    /// Most slots an account created through CPI can hold.
+    pub const MAX_SLOTS: u16 = ((10_240 - Self::LEN) / size_of::<TallySlot>()) as u16;
+
+    /// Account size of a tally with `slot_count` slots.
+    #[inline(always)]
+    pub const fn space(slot_count: u16) -> usize {
+        Self::LEN + slot_count as usize * size_of::<TallySlot>()
+    }
+
+    /// Number of slots in an account of `len` bytes, which must be a header
+    /// followed by whole slots.
+    #[inline(always)]
+    fn slots_in(len: usize) -> Result<usize, ProgramError> {
+        match len.checked_sub(Self::LEN) {
+            Some(slots_len) if slots_len % size_of::<TallySlot>() == 0 => {
+                Ok(slots_len / size_of::<TallySlot>())
+            }
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+
+    /// Without `check_key` the slot count is taken from the length of
+    /// `bytes`, so a freshly allocated account can be initialized.
+    #[inline(always)]
+    pub fn load_mut(
+        bytes: &mut [u8],
+        check_key: bool,
+    ) -> Result<(&mut Self, &mut [TallySlot]), ProgramError> {
+        let slot_count = Self::slots_in(bytes.len())?;
+        if check_key && bytes[0] != StateKey::Tally as u8 {
+            return Err(CounterError::NotInitialized.into());
+        }
+        let (header, slots) = bytes.split_at_mut(Self::LEN);
+        // SAFETY: `Tally` and `TallySlot` have alignment 1 and no padding,
+        // `header` is exactly `Tally::LEN` bytes and `slots` holds exactly
+        // `slot_count` `TallySlot`s.
+        let (tally, slots) = unsafe {
+            (
+                &mut *(header.as_mut_ptr() as *mut Self),
+                core::slice::from_raw_parts_mut(slots.as_mut_ptr() as *mut TallySlot, slot_count),
+            )
+        };
+        if check_key && tally.slot_count() as usize != slot_count {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok((tally, slots))
+    }
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<(&Self, &[TallySlot]), ProgramError> {
+        let slot_count = Self::slots_in(bytes.len())?;
+        if bytes[0] != StateKey::Tally as u8 {
+            return Err(CounterError::NotInitialized.into());
+        }
+        let (header, slots) = bytes.split_at(Self::LEN);
+        // SAFETY: see `load_mut`.
+        let (tally, slots) = unsafe {
+            (
+                &*(header.as_ptr() as *const Self),
+                core::slice::from_raw_parts(slots.as_ptr() as *const TallySlot, slot_count),
+            )
+        };
+        if tally.slot_count() as usize != slot_count {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok((tally, slots))
+    }
+
+    #[inline(always)]
+    pub fn set_inner(&mut self, authority: Pubkey, bump: u8, seed: &[u8], slot_count: u16) {
+        self.key = StateKey::Tally as u8;
+        self.bump = bump;
+        self.authority = authority;
+        self.seed = [0; MAX_SEED_LEN];
+        self.seed[..seed.len()].copy_from_slice(seed);
+        self.seed_len = seed.len() as u8;
+        self.slot_count = slot_count.to_le_bytes();
+    }
+
+    #[inline(always)]
+    pub fn slot_count(&self) -> u16 {
+        u16::from_le_bytes(self.slot_count)
+    }
+
+    /// The user-supplied part of the PDA seeds.
+    #[inline(always)]
+    pub fn seed(&self) -> &[u8] {
+        &self.seed[..self.seed_len as usize]
+    }
+
+    #[inline(always)]
+    pub fn slot(slots: &[TallySlot], index: u16) -> Result<u64, ProgramError> {
+        slots
+            .get(index as usize)
+            .map(|slot| u64::from_le_bytes(*slot))
+            .ok_or_else(|| CounterError::InvalidSlot.into())
+    }
+
+    /// Adds `amount` to slot `index`.
+    #[inline(always)]
+    pub fn increment(slots: &mut [TallySlot], index: u16, amount: u64) -> Result<(), ProgramError> {
+        let slot = slots
+            .get_mut(index as usize)
+            .ok_or(CounterError::InvalidSlot)?;
+        let value = u64::from_le_bytes(*slot)
+            .checked_add(amount)
+            .ok_or(CounterError::ArithmeticOverflow)?;
+        *slot = value.to_le_bytes();
+        Ok(())
+    }
+
+    /// Sets every slot back to zero.
+    #[inline(always)]
+    pub fn reset(slots: &mut [TallySlot]) {
+        slots.fill([0; 8]);
+    }
+
+    #[inline(always)]
+    pub fn seeds<'a>(authority: &'a Pubkey, seed: &'a [u8]) -> [&'a [u8]; 3] {
+        [b"tally", authority.as_ref(), seed]
+    }
+
+    #[inline(always)]
+    pub fn seeds_with_bump<'a>(
+        authority: &'a Pubkey,
+        seed: &'a [u8],
+        bump: &'a [u8],
+    ) -> [&'a [u8]; 4] {
+        let seeds = Self::seeds(authority, seed);
+        [seeds[0], seeds[1], seeds[2], bump]
+    }
+
+    #[inline(always)]
+    pub fn signer_seeds_with_bump<'a>(
+        authority: &'a Pubkey,
+        seed: &'a [u8],
+        bump: &'a [u8],
+    ) -> [Seed<'a>; 4] {
+        let seeds = Self::seeds_with_bump(authority, seed, bump);
+        [
+            Seed::from(seeds[0]),
+            Seed::from(seeds[1]),
+            Seed::from(seeds[2]),
+            Seed::from(seeds[3]),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slots_follow_the_header() {
+        // Offset by one byte so the tally never starts on an aligned address
+        let mut buffer = [0u8; Tally::space(3) + 1];
+        let bytes = &mut buffer[1..];
+
+        let (tally, slots) = Tally::load_mut(bytes, false).unwrap();
+        tally.set_inner([7; 32], 254, b"poll", 3);
+        Tally::increment(slots, 2, 5).unwrap();
+        Tally::increment(slots, 2, 1).unwrap();
+        assert_eq!(
+            Tally::increment(slots, 3, 1).unwrap_err(),
+            CounterError::InvalidSlot.into()
+        );
+        assert_eq!(
+            Tally::increment(slots, 2, u64::MAX).unwrap_err(),
+            CounterError::ArithmeticOverflow.into()
+        );
+
+        let (tally, slots) = Tally::load(bytes).unwrap();
+        assert_eq!(tally.slot_count(), 3);
+        assert_eq!(tally.seed(), b"poll");
+        assert_eq!(Tally::slot(slots, 0).unwrap(), 0);
+        assert_eq!(Tally::slot(slots, 2).unwrap(), 6);
+        assert_eq!(bytes[Tally::space(2)..], 6u64.to_le_bytes());
+
+        let (_, slots) = Tally::load_mut(bytes, true).unwrap();
+        Tally::reset(slots);
+        assert_eq!(Tally::slot(slots, 2).unwrap(), 0);
+    }
+
+    #[test]
+    fn load_rejects_mismatched_slot_count() {
+        let mut bytes = [0u8; Tally::space(2)];
+        Tally::load_mut(&mut bytes, false)
+            .unwrap()
+            .0
+            .set_inner([7; 32], 254, &[], 3);
+        assert_eq!(
+            Tally::load(&bytes).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+        assert_eq!(
+            Tally::load(&bytes[..Tally::space(2) - 1]).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+        assert_eq!(
+            Tally::load(&[0u8; Tally::LEN]).unwrap_err(),
+            CounterError::NotInitialized.into()
+        );
+    }
+}
@@ -0,0 +1,103 @@
+use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::state::{Counter, CounterV1};
+
+/// A counter account of any version this program can read.
+#[derive(Debug)]
+pub enum VersionedCounter<'a> {
+    V1(&'a CounterV1),
+    V2(&'a Counter),
+}
+
+impl<'a> VersionedCounter<'a> {
+    /// Loads `bytes` with the loader of the version they were written with.
+    #[inline(always)]
+    pub fn load(bytes: &'a [u8]) -> Result<Self, ProgramError> {
+        if bytes.len() == CounterV1::LEN {
+            return CounterV1::load(bytes).map(VersionedCounter::V1);
+        }
+        Counter::load(bytes).map(VersionedCounter::V2)
+    }
+
+    #[inline(always)]
+    pub fn version(&self) -> u8 {
+        match self {
+            VersionedCounter::V1(_) => CounterV1::VERSION,
+            VersionedCounter::V2(_) => Counter::VERSION,
+        }
+    }
+
+    #[inline(always)]
+    pub fn bump(&self) -> u8 {
+        match self {
+            VersionedCounter::V1(counter) => counter.bump,
+            VersionedCounter::V2(counter) => counter.bump,
+        }
+    }
+
+    #[inline(always)]
+    pub fn authority(&self) -> &Pubkey {
+        match self {
+            VersionedCounter::V1(counter) => &counter.authority,
+            VersionedCounter::V2(counter) => &counter.authority,
+        }
+    }
+
+    /// Authority the PDA was derived from; v1 counters were never transferred.
+    #[inline(always)]
+    pub fn seed_authority(&self) -> &Pubkey {
+        match self {
+            VersionedCounter::V1(counter) => &counter.authority,
+            VersionedCounter::V2(counter) => &counter.seed_authority,
+        }
+    }
+
+    /// V1 counters were derived from `[b"counter", authority]`, which is the
+    /// current scheme with an empty seed.
+    #[inline(always)]
+    pub fn seed(&self) -> &[u8] {
+        match self {
+            VersionedCounter::V1(_) => &[],
+            VersionedCounter::V2(counter) => counter.seed(),
+        }
+    }
+
+    #[inline(always)]
+    pub fn value(&self) -> u64 {
+        match self {
+            VersionedCounter::V1(counter) => counter.value(),
+            VersionedCounter::V2(counter) => counter.value(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::StateKey;
+
+    #[test]
+    fn load_dispatches_on_version() {
+        let mut v1 = [0u8; CounterV1::LEN];
+        v1[0] = StateKey::Counter as u8;
+        v1[40..42].copy_from_slice(&7u16.to_le_bytes());
+        let counter = VersionedCounter::load(&v1).unwrap();
+        assert_eq!(counter.version(), CounterV1::VERSION);
+        assert_eq!(counter.seed(), b"");
+        assert_eq!(counter.value(), 7);
+
+        let mut v2 = [0u8; Counter::LEN];
+        let counter = Counter::load_mut(&mut v2, false).unwrap();
+        counter.set_inner([7; 32], 254, b"metrics");
+        counter.set_value(9);
+        let counter = VersionedCounter::load(&v2).unwrap();
+        assert_eq!(counter.version(), Counter::VERSION);
+        assert_eq!(counter.seed(), b"metrics");
+        assert_eq!(counter.value(), 9);
+
+        assert_eq!(
+            VersionedCounter::load(&v2[1..]).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+    }
+}
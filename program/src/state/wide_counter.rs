@@ -0,0 +1,213 @@
+use core::{
+    mem::{align_of, size_of},
+    ops::{Deref, DerefMut},
+};
+use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{
+    error::CounterError,
+    return_data::WideValueChange,
+    state::{CounterHeader, CounterKind, StateKey},
+};
+
+/// Zero-copy view of a counter holding a `u128`, for totals that outgrow a
+/// `u64`.
+///
+/// Wide counters share the `Counter` PDA seeds and `CounterHeader` but
+/// have no limits or delegates. Like `Counter`, every field is a byte or
+/// byte array so the struct has an alignment of 1 and no padding.
+#[repr(C)]
+#[derive(Debug)]
+pub struct WideCounter {
+    pub header: CounterHeader,
+    value: [u8; 16],
+}
+
+const _: () = assert!(size_of::<WideCounter>() == WideCounter::LEN);
+const _: () = assert!(align_of::<WideCounter>() == 1);
+
+impl WideCounter {
+    pub const VERSION: u8 = 1;
+
+    pub const LEN: usize = CounterHeader::LEN + size_of::<u128>();
+
+    /// Fails unless `bytes` holds a counter of this kind and version. No
+    /// older version exists yet, so there is nothing to migrate from.
+    #[inline(always)]
+    fn check_layout(bytes: &[u8]) -> Result<(), ProgramError> {
+        if bytes.len() != WideCounter::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if bytes[0] != StateKey::WideCounter as u8 {
+            return Err(CounterError::NotInitialized.into());
+        }
+        if bytes[1] < WideCounter::VERSION {
+            return Err(CounterError::OutdatedVersion.into());
+        }
+        if bytes[1] != WideCounter::VERSION {
+            return Err(CounterError::UnsupportedVersion.into());
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8], check_key: bool) -> Result<&mut Self, ProgramError> {
+        if check_key {
+            Self::check_layout(bytes)?;
+        } else if bytes.len() != WideCounter::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        // SAFETY: `WideCounter` has alignment 1, no padding and is exactly
+        // `WideCounter::LEN` bytes, so any byte slice of that length is valid.
+        Ok(unsafe { &mut *(bytes.as_mut_ptr() as *mut Self) })
+    }
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        Self::check_layout(bytes)?;
+        // SAFETY: see `load_mut`.
+        Ok(unsafe { &*(bytes.as_ptr() as *const Self) })
+    }
+
+    #[inline(always)]
+    pub fn set_inner(&mut self, authority: Pubkey, bump: u8, seed: &[u8]) {
+        self.header.set_inner(
+            StateKey::WideCounter,
+            WideCounter::VERSION,
+            authority,
+            bump,
+            seed,
+        );
+        self.set_value(0);
+    }
+
+    #[inline(always)]
+    pub fn value(&self) -> u128 {
+        u128::from_le_bytes(self.value)
+    }
+
+    #[inline(always)]
+    pub fn set_value(&mut self, value: u128) {
+        self.value = value.to_le_bytes();
+    }
+
+    #[inline(always)]
+    pub fn increase_by(&mut self, amount: u64) -> Result<(), ProgramError> {
+        let value = self
+            .value()
+            .checked_add(amount as u128)
+            .ok_or(CounterError::ArithmeticOverflow)?;
+        self.set_value(value);
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn decrease_by(&mut self, amount: u64) -> Result<(), ProgramError> {
+        let value = self
+            .value()
+            .checked_sub(amount as u128)
+            .ok_or(CounterError::ArithmeticUnderflow)?;
+        self.set_value(value);
+        Ok(())
+    }
+}
+
+impl Deref for WideCounter {
+    type Target = CounterHeader;
+
+    #[inline(always)]
+    fn deref(&self) -> &CounterHeader {
+        &self.header
+    }
+}
+
+impl DerefMut for WideCounter {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut CounterHeader {
+        &mut self.header
+    }
+}
+
+impl CounterKind for WideCounter {
+    const KEY: StateKey = StateKey::WideCounter;
+
+    const LEN: usize = WideCounter::LEN;
+
+    type Value = u128;
+
+    type ValueChange = WideValueChange;
+
+    #[inline(always)]
+    fn load_mut(bytes: &mut [u8], check_key: bool) -> Result<&mut Self, ProgramError> {
+        WideCounter::load_mut(bytes, check_key)
+    }
+
+    #[inline(always)]
+    fn set_inner(&mut self, authority: Pubkey, bump: u8, seed: &[u8]) {
+        WideCounter::set_inner(self, authority, bump, seed)
+    }
+
+    #[inline(always)]
+    fn value(&self) -> u128 {
+        WideCounter::value(self)
+    }
+
+    #[inline(always)]
+    fn increase_by(&mut self, amount: u64) -> Result<(), ProgramError> {
+        WideCounter::increase_by(self, amount)
+    }
+
+    #[inline(always)]
+    fn decrease_by(&mut self, amount: u64) -> Result<(), ProgramError> {
+        WideCounter::decrease_by(self, amount)
+    }
+
+    /// Kinds without limits reset to zero.
+    #[inline(always)]
+    fn reset(&mut self) {
+        self.set_value(0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn value_grows_past_u64() {
+        let mut bytes = [0u8; WideCounter::LEN];
+        let counter = WideCounter::load_mut(&mut bytes, false).unwrap();
+        counter.set_inner([7; 32], 254, &[]);
+
+        counter.increase_by(u64::MAX).unwrap();
+        counter.increase_by(u64::MAX).unwrap();
+        assert_eq!(counter.value(), 2 * u64::MAX as u128);
+        counter.decrease_by(u64::MAX).unwrap();
+        assert_eq!(counter.value(), u64::MAX as u128);
+
+        counter.set_value(u128::MAX);
+        assert_eq!(
+            counter.increase_by(1).unwrap_err(),
+            CounterError::ArithmeticOverflow.into()
+        );
+        counter.set_value(0);
+        assert_eq!(
+            counter.decrease_by(1).unwrap_err(),
+            CounterError::ArithmeticUnderflow.into()
+        );
+
+        // The value is the trailing little-endian u128
+        counter.set_value(u128::MAX - 1);
+        assert_eq!(
+            bytes[WideCounter::LEN - 16..],
+            (u128::MAX - 1).to_le_bytes()
+        );
+        assert_eq!(WideCounter::load(&bytes).unwrap().value(), u128::MAX - 1);
+
+        bytes[1] = WideCounter::VERSION + 1;
+        assert_eq!(
+            WideCounter::load(&bytes).unwrap_err(),
+            CounterError::UnsupportedVersion.into()
+        );
+    }
+}
@@ -1,6 +1,19 @@
 use counter_pinocchio::{
-    instructions::{Create, Increase},
-    state::{to_bytes, Counter, StateKey},
+    error::CounterError,
+    events::{CounterEvent, EventKind, EventValue},
+    helpers::ProgramDataAccount,
+    instructions::{
+        AcceptAuthority, ApproveDelegate, BatchIncrease, Close, CompareAndSet, Configure,
+        ConfigureRateLimit, Create, CreateIdempotent, CreateSigned, CreateTally, CreateWide,
+        Decrease, Freeze, Increase, IncrementTally, InitializeConfig, InitializeHistory,
+        InitializeMultisig, Migrate, ProposeAuthority, Reset, ResetTally, RevokeDelegate, Set,
+        Thaw, UpdateConfig,
+    },
+    return_data::{BatchValues, SignedValueChange, ValueChange, WideValueChange},
+    state::{
+        Config, Counter, CounterStatus, CounterV1, Delegate, History, HistoryEntry, Limits,
+        Multisig, RateLimit, SignedCounter, StateKey, Tally, WideCounter,
+    },
     ID,
 };
 use mollusk_svm::{
@@ -13,6 +26,7 @@ use solana_sdk::{
     account::Account,
     instruction::{AccountMeta, Instruction},
     native_token::LAMPORTS_PER_SOL,
+    program_error::ProgramError,
     pubkey::Pubkey,
 };
 extern crate alloc;
@@ -20,10 +34,408 @@ use alloc::vec;
 
 pub const PROGRAM: Pubkey = Pubkey::new_from_array(ID);
 pub const AUTHORITY: Pubkey = pubkey!("Co11111111111111111111111111111111111111111");
+pub const OTHER_AUTHORITY: Pubkey = pubkey!("Co22222222222222222222222222222222222222222");
 
 pub fn mollusk() -> Mollusk {
-    let mollusk = Mollusk::new(&PROGRAM, "../target/deploy/counter_pinocchio");
-    mollusk
+    Mollusk::new(&PROGRAM, "../target/deploy/counter_pinocchio")
+}
+
+fn counter_error(error: CounterError) -> ProgramError {
+    ProgramError::Custom(error as u32)
+}
+
+fn config_pda() -> Pubkey {
+    Pubkey::find_program_address(&Config::seeds(), &PROGRAM).0
+}
+
+/// The config PDA before `InitializeConfig`, which leaves the program
+/// unpaused with default policies.
+fn keyed_config_account() -> (Pubkey, Account) {
+    (config_pda(), Account::default())
+}
+
+/// A zeroed account of `len` bytes, rent exempt and owned by the program.
+fn program_account(mollusk: &Mollusk, len: usize) -> Account {
+    Account::new(mollusk.sysvars.rent.minimum_balance(len), len, &PROGRAM)
+}
+
+/// Builds an initialized config account administered by `admin`.
+fn config_account(
+    mollusk: &Mollusk,
+    admin: &Pubkey,
+    paused: bool,
+    default_max_step: u64,
+) -> Account {
+    let (_, bump) = Pubkey::find_program_address(&Config::seeds(), &PROGRAM);
+    let mut config_account = program_account(mollusk, Config::LEN);
+
+    let config = Config::load_mut(&mut config_account.data, false).unwrap();
+    config.set_inner(admin.to_bytes(), bump, default_max_step);
+    config.set_paused(paused);
+    config_account
+}
+
+fn update_config_instruction(
+    admin: Pubkey,
+    new_admin: Pubkey,
+    paused: bool,
+    default_max_step: u64,
+) -> Instruction {
+    let mut data = vec![*UpdateConfig::DISCRIMINATOR];
+    data.extend_from_slice(new_admin.as_ref());
+    data.push(paused as u8);
+    data.extend_from_slice(&default_max_step.to_le_bytes());
+    Instruction::new_with_bytes(
+        PROGRAM,
+        &data,
+        vec![
+            AccountMeta::new(config_pda(), false),
+            AccountMeta::new_readonly(admin, true),
+        ],
+    )
+}
+
+/// Builds an `m`-of-`signers.len()` multisig account created by `creator`.
+fn multisig_account(mollusk: &Mollusk, creator: &Pubkey, m: u8, signers: &[Pubkey]) -> Account {
+    let (_, bump) =
+        Pubkey::find_program_address(&Multisig::seeds(&creator.to_bytes(), &[]), &PROGRAM);
+    let mut multisig_account = program_account(mollusk, Multisig::LEN);
+
+    let signers: Vec<[u8; 32]> = signers.iter().map(|signer| signer.to_bytes()).collect();
+    Multisig::load_mut(&mut multisig_account.data, false)
+        .unwrap()
+        .set_inner(bump, m, signers.iter())
+        .unwrap();
+    multisig_account
+}
+
+/// Builds a program-owned counter account whose data is filled in by `init`.
+fn counter_account_with(mollusk: &Mollusk, init: impl FnOnce(&mut Counter)) -> Account {
+    let mut counter_account = program_account(mollusk, Counter::LEN);
+    init(Counter::load_mut(&mut counter_account.data, false).unwrap());
+    counter_account
+}
+
+/// Builds an initialized counter account owned by the program.
+fn counter_account(mollusk: &Mollusk, authority: &Pubkey, bump: u8, value: u64) -> Account {
+    updated_counter_account(mollusk, authority, bump, value, 0)
+}
+
+/// Builds the counter left behind by `updates` value changes at the default
+/// clock, ending at `value`.
+fn updated_counter_account(
+    mollusk: &Mollusk,
+    authority: &Pubkey,
+    bump: u8,
+    value: u64,
+    updates: u64,
+) -> Account {
+    counter_account_with(mollusk, |counter| {
+        counter.set_inner(authority.to_bytes(), bump, &[]);
+        counter.set_value(value);
+        for _ in 0..updates {
+            counter.record_update(0, 0);
+        }
+    })
+}
+
+/// Instruction taking `[counter, authority, config]`, the accounts every
+/// instruction on an existing counter or tally starts with.
+fn authority_instruction(
+    discriminator: u8,
+    data: &[u8],
+    counter: Pubkey,
+    authority: Pubkey,
+    signer: bool,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        PROGRAM,
+        &[&[discriminator], data].concat(),
+        vec![
+            AccountMeta::new(counter, false),
+            AccountMeta::new(authority, signer),
+            AccountMeta::new_readonly(config_pda(), false),
+        ],
+    )
+}
+
+/// Appends `accounts` to `instruction`.
+fn with_accounts(
+    mut instruction: Instruction,
+    accounts: impl IntoIterator<Item = AccountMeta>,
+) -> Instruction {
+    instruction.accounts.extend(accounts);
+    instruction
+}
+
+/// Appends the signing members of a multisig authority to `instruction`.
+fn with_multisig_signers(instruction: Instruction, signers: &[Pubkey]) -> Instruction {
+    with_accounts(
+        instruction,
+        signers
+            .iter()
+            .map(|signer| AccountMeta::new_readonly(*signer, true)),
+    )
+}
+
+/// Appends the history of `counter` to `instruction`, so the change is
+/// recorded.
+fn with_history(instruction: Instruction, counter: Pubkey) -> Instruction {
+    let (history_pda, _) =
+        Pubkey::find_program_address(&History::seeds(&counter.to_bytes()), &PROGRAM);
+    with_accounts(instruction, [AccountMeta::new(history_pda, false)])
+}
+
+/// `Create`, `CreateSigned` or `CreateWide` of the counter of `authority`.
+fn create_instruction(discriminator: u8, counter: Pubkey, authority: Pubkey) -> Instruction {
+    with_accounts(
+        authority_instruction(discriminator, &[], counter, authority, true),
+        [AccountMeta::new_readonly(
+            solana_sdk::system_program::ID,
+            false,
+        )],
+    )
+}
+
+fn increase_instruction(
+    counter: Pubkey,
+    authority: Pubkey,
+    signer: bool,
+    amount: u64,
+) -> Instruction {
+    authority_instruction(
+        *Increase::DISCRIMINATOR,
+        &amount.to_le_bytes(),
+        counter,
+        authority,
+        signer,
+    )
+}
+
+fn decrease_instruction(
+    counter: Pubkey,
+    authority: Pubkey,
+    signer: bool,
+    amount: u64,
+) -> Instruction {
+    authority_instruction(
+        *Decrease::DISCRIMINATOR,
+        &amount.to_le_bytes(),
+        counter,
+        authority,
+        signer,
+    )
+}
+
+fn reset_instruction(counter: Pubkey, authority: Pubkey, signer: bool) -> Instruction {
+    authority_instruction(*Reset::DISCRIMINATOR, &[], counter, authority, signer)
+}
+
+fn close_instruction(counter: Pubkey, authority: Pubkey, recipient: Pubkey) -> Instruction {
+    with_accounts(
+        authority_instruction(*Close::DISCRIMINATOR, &[], counter, authority, true),
+        [AccountMeta::new(recipient, false)],
+    )
+}
+
+/// The `Delegate` account of `delegate` on `counter`.
+fn delegate_pda(counter: Pubkey, delegate: Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &Delegate::seeds(&counter.to_bytes(), &delegate.to_bytes()),
+        &PROGRAM,
+    )
+    .0
+}
+
+fn approve_delegate_instruction(
+    counter: Pubkey,
+    authority: Pubkey,
+    signer: bool,
+    payer: Pubkey,
+    delegate: Pubkey,
+    allowance: u64,
+    expiry_slot: u64,
+) -> Instruction {
+    let data = [
+        delegate.as_ref(),
+        &allowance.to_le_bytes(),
+        &expiry_slot.to_le_bytes(),
+    ]
+    .concat();
+    with_accounts(
+        authority_instruction(
+            *ApproveDelegate::DISCRIMINATOR,
+            &data,
+            counter,
+            authority,
+            signer,
+        ),
+        [
+            AccountMeta::new(delegate_pda(counter, delegate), false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(solana_sdk::system_program::ID, false),
+        ],
+    )
+}
+
+fn revoke_delegate_instruction(
+    counter: Pubkey,
+    authority: Pubkey,
+    signer: bool,
+    delegate: Pubkey,
+    destination: Pubkey,
+) -> Instruction {
+    with_accounts(
+        authority_instruction(
+            *RevokeDelegate::DISCRIMINATOR,
+            &[],
+            counter,
+            authority,
+            signer,
+        ),
+        [
+            AccountMeta::new(delegate_pda(counter, delegate), false),
+            AccountMeta::new(destination, false),
+        ],
+    )
+}
+
+fn initialize_history_instruction(
+    counter: Pubkey,
+    authority: Pubkey,
+    signer: bool,
+    payer: Pubkey,
+) -> Instruction {
+    let (history_pda, _) =
+        Pubkey::find_program_address(&History::seeds(&counter.to_bytes()), &PROGRAM);
+    with_accounts(
+        authority_instruction(
+            *InitializeHistory::DISCRIMINATOR,
+            &[],
+            counter,
+            authority,
+            signer,
+        ),
+        [
+            AccountMeta::new(history_pda, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(solana_sdk::system_program::ID, false),
+        ],
+    )
+}
+
+fn delegate_increase_instruction(counter: Pubkey, delegate: Pubkey, amount: u64) -> Instruction {
+    let mut instruction = with_accounts(
+        increase_instruction(counter, delegate, true, amount),
+        [AccountMeta::new(delegate_pda(counter, delegate), false)],
+    );
+    // The delegate only signs; it pays for nothing
+    instruction.accounts[1].is_writable = false;
+    instruction
+}
+
+/// `BatchIncrease` of `counters[i]` by `amounts[i]`.
+fn batch_increase_instruction(
+    counters: &[Pubkey],
+    authority: Pubkey,
+    amounts: &[u64],
+) -> Instruction {
+    let mut ix_data = vec![*BatchIncrease::DISCRIMINATOR];
+    for amount in amounts {
+        ix_data.extend_from_slice(&amount.to_le_bytes());
+    }
+
+    let mut accounts: Vec<AccountMeta> = counters
+        .iter()
+        .map(|counter| AccountMeta::new(*counter, false))
+        .collect();
+    accounts.push(AccountMeta::new_readonly(authority, true));
+    accounts.push(AccountMeta::new_readonly(config_pda(), false));
+    Instruction::new_with_bytes(PROGRAM, &ix_data, accounts)
+}
+
+/// Builds the counter of `authority` named by `index`, as the CLI does with
+/// `--index`.
+fn indexed_counter(
+    mollusk: &Mollusk,
+    authority: &Pubkey,
+    index: u64,
+    value: u64,
+    updates: u64,
+) -> (Pubkey, Account) {
+    let seed = index.to_le_bytes();
+    let (counter_pda, bump) =
+        Pubkey::find_program_address(&Counter::seeds(&authority.to_bytes(), &seed), &PROGRAM);
+    let account = counter_account_with(mollusk, |counter| {
+        counter.set_inner(authority.to_bytes(), bump, &seed);
+        counter.set_value(value);
+        for _ in 0..updates {
+            counter.record_update(0, 0);
+        }
+    });
+    (counter_pda, account)
+}
+
+/// The tally of `authority` under `seed`, with `slots` as its slot values.
+fn tally_account(
+    mollusk: &Mollusk,
+    authority: &Pubkey,
+    seed: &[u8],
+    slots: &[u64],
+) -> (Pubkey, Account) {
+    let (tally_pda, bump) =
+        Pubkey::find_program_address(&Tally::seeds(&authority.to_bytes(), seed), &PROGRAM);
+    let slot_count = slots.len() as u16;
+    let mut data = vec![0; Tally::space(slot_count)];
+    let (tally, tally_slots) = Tally::load_mut(&mut data, false).unwrap();
+    tally.set_inner(authority.to_bytes(), bump, seed, slot_count);
+    for (slot, value) in tally_slots.iter_mut().zip(slots) {
+        *slot = value.to_le_bytes();
+    }
+    let mut account = program_account(mollusk, data.len());
+    account.data = data;
+    (tally_pda, account)
+}
+
+fn create_tally_instruction(
+    tally: Pubkey,
+    authority: Pubkey,
+    slot_count: u16,
+    seed: &[u8],
+) -> Instruction {
+    with_accounts(
+        authority_instruction(
+            *CreateTally::DISCRIMINATOR,
+            &[&slot_count.to_le_bytes(), seed].concat(),
+            tally,
+            authority,
+            true,
+        ),
+        [AccountMeta::new_readonly(
+            solana_sdk::system_program::ID,
+            false,
+        )],
+    )
+}
+
+fn increment_tally_instruction(
+    tally: Pubkey,
+    authority: Pubkey,
+    signer: bool,
+    index: u16,
+    amount: u64,
+) -> Instruction {
+    authority_instruction(
+        *IncrementTally::DISCRIMINATOR,
+        &[&index.to_le_bytes()[..], &amount.to_le_bytes()].concat(),
+        tally,
+        authority,
+        signer,
+    )
+}
+
+fn reset_tally_instruction(tally: Pubkey, authority: Pubkey, signer: bool) -> Instruction {
+    authority_instruction(*ResetTally::DISCRIMINATOR, &[], tally, authority, signer)
 }
 
 #[test]
@@ -35,16 +447,17 @@ fn test_create_counter() {
 
     // Create the PDA
     let (counter_pda, _bump) =
-        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes()), &PROGRAM);
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
 
     //Initialize the accounts
-    let authority_account = Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program);
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
     let counter_account = Account::new(0, 0, &system_program);
 
     //Push the accounts in to the instruction_accounts vec!
     let ix_accounts = vec![
         AccountMeta::new(counter_pda, false),             // counter
         AccountMeta::new(AUTHORITY, true),                // authority
+        AccountMeta::new_readonly(config_pda(), false),   // config
         AccountMeta::new_readonly(system_program, false), // system program
     ];
 
@@ -54,7 +467,8 @@ fn test_create_counter() {
     let instruction = Instruction::new_with_bytes(PROGRAM, &ix_data, ix_accounts);
 
     // Create tx_accounts vec
-    let tx_accounts = &vec![
+    let tx_accounts = &[
+        keyed_config_account(),
         (counter_pda, counter_account.clone()),
         (AUTHORITY, authority_account.clone()),
         (system_program, system_account.clone()),
@@ -73,48 +487,2672 @@ fn test_increase_counter() {
     //system program and system account
     let (system_program, _system_account) = program::keyed_account_for_system_program();
 
-    // Create the PDA
     // Create the PDA
     let (counter_pda, bump) =
-        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes()), &PROGRAM);
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
 
     //Initialize the accounts
-    let authority_account = Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program);
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let counter_account = counter_account(&mollusk, &AUTHORITY, bump, 0);
+
+    // Create instruction
+    let instruction = increase_instruction(counter_pda, AUTHORITY, true, 1);
+
+    // Create tx_accounts vec
+    let tx_accounts = &[
+        keyed_config_account(),
+        (counter_pda, counter_account.clone()),
+        (AUTHORITY, authority_account.clone()),
+    ];
+
+    let update_res =
+        mollusk.process_and_validate_instruction(&instruction, tx_accounts, &[Check::success()]);
+
+    assert!(update_res.program_result == ProgramResult::Success);
+}
+
+#[test]
+fn test_increase_counter_returns_value_change() {
+    let mollusk = mollusk();
+
+    let (system_program, _system_account) = program::keyed_account_for_system_program();
+
+    let (counter_pda, bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let counter_account = counter_account(&mollusk, &AUTHORITY, bump, 40);
+
+    let instruction = increase_instruction(counter_pda, AUTHORITY, true, 2);
 
-    let mut counter_account = Account::new(
-        mollusk.sysvars.rent.minimum_balance(Counter::LEN),
-        Counter::LEN,
-        &ID.into(),
+    let tx_accounts = &[
+        keyed_config_account(),
+        (counter_pda, counter_account.clone()),
+        (AUTHORITY, authority_account.clone()),
+    ];
+
+    // New value first, then the previous one, both little-endian
+    let mut expected = 42u64.to_le_bytes().to_vec();
+    expected.extend_from_slice(&40u64.to_le_bytes());
+
+    let result = mollusk.process_and_validate_instruction(
+        &instruction,
+        tx_accounts,
+        &[Check::success(), Check::return_data(&expected)],
     );
 
-    let counter = Counter {
-        authority: AUTHORITY.to_bytes(),
-        bump,
-        key: StateKey::Counter,
-        value: 0,
-    };
+    assert_eq!(
+        ValueChange::from_bytes(&result.return_data),
+        Some(ValueChange {
+            previous: 40,
+            new: 42
+        })
+    );
+}
 
-    counter_account.data = unsafe { to_bytes(&counter, Counter::LEN).to_vec() };
+#[test]
+fn test_increase_counter_without_authority_signature_fails() {
+    let mollusk = mollusk();
 
-    //Push the accounts in to the instruction_accounts vec!
-    let ix_accounts = vec![
-        AccountMeta::new(counter_pda, false),
-        AccountMeta::new(AUTHORITY, true),
+    let (system_program, _system_account) = program::keyed_account_for_system_program();
+
+    let (counter_pda, bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let counter_account = counter_account(&mollusk, &AUTHORITY, bump, 0);
+
+    // Authority is passed but does not sign
+    let instruction = increase_instruction(counter_pda, AUTHORITY, false, 1);
+
+    let tx_accounts = &[
+        keyed_config_account(),
+        (counter_pda, counter_account.clone()),
+        (AUTHORITY, authority_account.clone()),
     ];
 
-    let mut ix_data = vec![*Increase::DISCRIMINATOR];
-    ix_data.extend_from_slice(&1u64.to_le_bytes());
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        tx_accounts,
+        &[Check::err(ProgramError::MissingRequiredSignature)],
+    );
+}
 
-    // Create instruction
-    let instruction = Instruction::new_with_bytes(PROGRAM, &ix_data, ix_accounts);
-    // Create tx_accounts vec
-    let tx_accounts = &vec![
+#[test]
+fn test_increase_counter_with_other_signer_fails() {
+    let mollusk = mollusk();
+
+    let (system_program, _system_account) = program::keyed_account_for_system_program();
+
+    let (counter_pda, bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+
+    let other_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let counter_account = counter_account(&mollusk, &AUTHORITY, bump, 0);
+
+    // A different key signs in place of the authority
+    let instruction = increase_instruction(counter_pda, OTHER_AUTHORITY, true, 1);
+
+    let tx_accounts = &[
+        keyed_config_account(),
+        (counter_pda, counter_account.clone()),
+        (OTHER_AUTHORITY, other_account.clone()),
+    ];
+
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        tx_accounts,
+        &[Check::err(counter_error(CounterError::InvalidAuthority))],
+    );
+}
+
+#[test]
+fn test_increase_counter_with_mismatched_stored_authority_fails() {
+    let mollusk = mollusk();
+
+    let (system_program, _system_account) = program::keyed_account_for_system_program();
+
+    let (counter_pda, bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    // The PDA is derived from AUTHORITY but the stored authority is someone else
+    let counter_account = counter_account_with(&mollusk, |counter| {
+        counter.set_inner(AUTHORITY.to_bytes(), bump, &[]);
+        counter.authority = OTHER_AUTHORITY.to_bytes();
+    });
+
+    let instruction = increase_instruction(counter_pda, AUTHORITY, true, 1);
+
+    let tx_accounts = &[
+        keyed_config_account(),
         (counter_pda, counter_account.clone()),
         (AUTHORITY, authority_account.clone()),
     ];
 
-    let update_res =
-        mollusk.process_and_validate_instruction(&instruction, tx_accounts, &[Check::success()]);
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        tx_accounts,
+        &[Check::err(counter_error(CounterError::InvalidAuthority))],
+    );
+}
 
-    assert!(update_res.program_result == ProgramResult::Success);
+#[test]
+fn test_decrease_counter() {
+    let mollusk = mollusk();
+
+    let (system_program, _system_account) = program::keyed_account_for_system_program();
+
+    let (counter_pda, bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let expected = updated_counter_account(&mollusk, &AUTHORITY, bump, 3, 1);
+    let counter_account = counter_account(&mollusk, &AUTHORITY, bump, 5);
+
+    let instruction = decrease_instruction(counter_pda, AUTHORITY, true, 2);
+
+    let tx_accounts = &[
+        keyed_config_account(),
+        (counter_pda, counter_account.clone()),
+        (AUTHORITY, authority_account.clone()),
+    ];
+
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        tx_accounts,
+        &[
+            Check::success(),
+            Check::account(&counter_pda).data(&expected.data).build(),
+            Check::return_data(
+                &ValueChange {
+                    previous: 5,
+                    new: 3,
+                }
+                .to_bytes(),
+            ),
+        ],
+    );
+}
+
+#[test]
+fn test_decrease_counter_underflow_fails() {
+    let mollusk = mollusk();
+
+    let (system_program, _system_account) = program::keyed_account_for_system_program();
+
+    let (counter_pda, bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let counter_account = counter_account(&mollusk, &AUTHORITY, bump, 1);
+
+    let instruction = decrease_instruction(counter_pda, AUTHORITY, true, 2);
+
+    let tx_accounts = &[
+        keyed_config_account(),
+        (counter_pda, counter_account.clone()),
+        (AUTHORITY, authority_account.clone()),
+    ];
+
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        tx_accounts,
+        &[Check::err(counter_error(CounterError::ArithmeticUnderflow))],
+    );
+}
+
+#[test]
+fn test_set_counter() {
+    let mollusk = mollusk();
+
+    let (system_program, _system_account) = program::keyed_account_for_system_program();
+
+    let (counter_pda, bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let expected = updated_counter_account(&mollusk, &AUTHORITY, bump, 42, 1);
+    let counter_account = counter_account(&mollusk, &AUTHORITY, bump, 7);
+
+    let instruction = authority_instruction(
+        *Set::DISCRIMINATOR,
+        &42u64.to_le_bytes(),
+        counter_pda,
+        AUTHORITY,
+        true,
+    );
+
+    let tx_accounts = &[
+        keyed_config_account(),
+        (counter_pda, counter_account.clone()),
+        (AUTHORITY, authority_account.clone()),
+    ];
+
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        tx_accounts,
+        &[
+            Check::success(),
+            Check::account(&counter_pda).data(&expected.data).build(),
+        ],
+    );
+}
+
+#[test]
+fn test_reset_counter() {
+    let mollusk = mollusk();
+
+    let (system_program, _system_account) = program::keyed_account_for_system_program();
+
+    let (counter_pda, bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let expected = updated_counter_account(&mollusk, &AUTHORITY, bump, 0, 1);
+    let counter_account = counter_account(&mollusk, &AUTHORITY, bump, 7);
+
+    let instruction = reset_instruction(counter_pda, AUTHORITY, true);
+
+    let tx_accounts = &[
+        keyed_config_account(),
+        (counter_pda, counter_account.clone()),
+        (AUTHORITY, authority_account.clone()),
+    ];
+
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        tx_accounts,
+        &[
+            Check::success(),
+            Check::account(&counter_pda).data(&expected.data).build(),
+        ],
+    );
+}
+
+#[test]
+fn test_reset_and_set_without_authority_signature_fail() {
+    let mollusk = mollusk();
+
+    let (system_program, _system_account) = program::keyed_account_for_system_program();
+
+    let (counter_pda, bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let counter_account = counter_account(&mollusk, &AUTHORITY, bump, 7);
+
+    let tx_accounts = &[
+        keyed_config_account(),
+        (counter_pda, counter_account.clone()),
+        (AUTHORITY, authority_account.clone()),
+    ];
+
+    for instruction in [
+        reset_instruction(counter_pda, AUTHORITY, false),
+        authority_instruction(
+            *Set::DISCRIMINATOR,
+            &42u64.to_le_bytes(),
+            counter_pda,
+            AUTHORITY,
+            false,
+        ),
+    ] {
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            tx_accounts,
+            &[Check::err(ProgramError::MissingRequiredSignature)],
+        );
+    }
+}
+
+#[test]
+fn test_close_counter() {
+    let mollusk = mollusk();
+
+    let (system_program, _system_account) = program::keyed_account_for_system_program();
+
+    let (counter_pda, bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let recipient_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let counter_account = counter_account(&mollusk, &AUTHORITY, bump, 7);
+
+    let instruction = close_instruction(counter_pda, AUTHORITY, OTHER_AUTHORITY);
+
+    let tx_accounts = &[
+        keyed_config_account(),
+        (counter_pda, counter_account.clone()),
+        (AUTHORITY, authority_account.clone()),
+        (OTHER_AUTHORITY, recipient_account.clone()),
+    ];
+
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        tx_accounts,
+        &[
+            Check::success(),
+            Check::account(&counter_pda).closed().build(),
+            Check::account(&OTHER_AUTHORITY)
+                .lamports(LAMPORTS_PER_SOL + counter_account.lamports)
+                .build(),
+        ],
+    );
+}
+
+#[test]
+fn test_close_counter_then_recreate() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let (counter_pda, bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let expected = counter_account(&mollusk, &AUTHORITY, bump, 0);
+    let counter_account = counter_account(&mollusk, &AUTHORITY, bump, 7);
+
+    let close = close_instruction(counter_pda, AUTHORITY, AUTHORITY);
+    let create = Instruction::new_with_bytes(
+        PROGRAM,
+        &[*Create::DISCRIMINATOR],
+        vec![
+            AccountMeta::new(counter_pda, false),
+            AccountMeta::new(AUTHORITY, true),
+            AccountMeta::new_readonly(config_pda(), false),
+            AccountMeta::new_readonly(system_program, false),
+        ],
+    );
+
+    let tx_accounts = &[
+        keyed_config_account(),
+        (counter_pda, counter_account.clone()),
+        (AUTHORITY, authority_account.clone()),
+        (system_program, system_account.clone()),
+    ];
+
+    mollusk.process_and_validate_instruction_chain(
+        &[
+            (&close, &[Check::success()]),
+            (
+                &create,
+                &[
+                    Check::success(),
+                    Check::account(&counter_pda).data(&expected.data).build(),
+                ],
+            ),
+        ],
+        tx_accounts,
+    );
+}
+
+#[test]
+fn test_close_counter_without_authority_signature_fails() {
+    let mollusk = mollusk();
+
+    let (system_program, _system_account) = program::keyed_account_for_system_program();
+
+    let (counter_pda, bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let counter_account = counter_account(&mollusk, &AUTHORITY, bump, 7);
+
+    let recipient_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+
+    let mut instruction = close_instruction(counter_pda, AUTHORITY, OTHER_AUTHORITY);
+    instruction.accounts[1].is_signer = false;
+
+    let tx_accounts = &[
+        keyed_config_account(),
+        (counter_pda, counter_account.clone()),
+        (AUTHORITY, authority_account.clone()),
+        (OTHER_AUTHORITY, recipient_account.clone()),
+    ];
+
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        tx_accounts,
+        &[Check::err(ProgramError::MissingRequiredSignature)],
+    );
+}
+
+#[test]
+fn test_propose_and_accept_authority() {
+    let mollusk = mollusk();
+
+    let (system_program, _system_account) = program::keyed_account_for_system_program();
+
+    let (counter_pda, bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let new_authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let counter_account = counter_account(&mollusk, &AUTHORITY, bump, 0);
+
+    // The PDA keeps its original seeds, only the stored authority moves
+    let transferred = counter_account_with(&mollusk, |counter| {
+        counter.set_inner(AUTHORITY.to_bytes(), bump, &[]);
+        counter.authority = OTHER_AUTHORITY.to_bytes();
+        counter.set_value(1);
+    });
+
+    let propose = authority_instruction(
+        *ProposeAuthority::DISCRIMINATOR,
+        OTHER_AUTHORITY.as_ref(),
+        counter_pda,
+        AUTHORITY,
+        true,
+    );
+    let accept = authority_instruction(
+        *AcceptAuthority::DISCRIMINATOR,
+        &[],
+        counter_pda,
+        OTHER_AUTHORITY,
+        true,
+    );
+    let increase_by_old = increase_instruction(counter_pda, AUTHORITY, true, 1);
+    let increase_by_new = increase_instruction(counter_pda, OTHER_AUTHORITY, true, 1);
+
+    let tx_accounts = &[
+        keyed_config_account(),
+        (counter_pda, counter_account.clone()),
+        (AUTHORITY, authority_account.clone()),
+        (OTHER_AUTHORITY, new_authority_account.clone()),
+    ];
+
+    mollusk.process_and_validate_instruction_chain(
+        &[
+            (&propose, &[Check::success()]),
+            (&accept, &[Check::success()]),
+            (&increase_by_new, &[Check::success()]),
+            (
+                &increase_by_old,
+                &[
+                    Check::err(counter_error(CounterError::InvalidAuthority)),
+                    Check::account(&counter_pda).data(&transferred.data).build(),
+                ],
+            ),
+        ],
+        tx_accounts,
+    );
+}
+
+#[test]
+fn test_accept_authority_without_proposal_fails() {
+    let mollusk = mollusk();
+
+    let (system_program, _system_account) = program::keyed_account_for_system_program();
+
+    let (counter_pda, bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+
+    let new_authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let counter_account = counter_account(&mollusk, &AUTHORITY, bump, 0);
+
+    let instruction = authority_instruction(
+        *AcceptAuthority::DISCRIMINATOR,
+        &[],
+        counter_pda,
+        OTHER_AUTHORITY,
+        true,
+    );
+
+    let tx_accounts = &[
+        keyed_config_account(),
+        (counter_pda, counter_account.clone()),
+        (OTHER_AUTHORITY, new_authority_account.clone()),
+    ];
+
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        tx_accounts,
+        &[Check::err(counter_error(CounterError::NoPendingAuthority))],
+    );
+}
+
+#[test]
+fn test_create_and_increase_named_counter() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let name = b"requests";
+    let (counter_pda, bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), name), &PROGRAM);
+
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let counter_account = Account::new(0, 0, &system_program);
+
+    let expected = counter_account_with(&mollusk, |counter| {
+        counter.set_inner(AUTHORITY.to_bytes(), bump, name);
+        counter.set_value(3);
+        counter.record_update(0, 0);
+    });
+
+    let mut ix_data = vec![*Create::DISCRIMINATOR, name.len() as u8];
+    ix_data.extend_from_slice(name);
+    let create = Instruction::new_with_bytes(
+        PROGRAM,
+        &ix_data,
+        vec![
+            AccountMeta::new(counter_pda, false),
+            AccountMeta::new(AUTHORITY, true),
+            AccountMeta::new_readonly(config_pda(), false),
+            AccountMeta::new_readonly(system_program, false),
+        ],
+    );
+    let increase = increase_instruction(counter_pda, AUTHORITY, true, 3);
+
+    let tx_accounts = &[
+        keyed_config_account(),
+        (counter_pda, counter_account.clone()),
+        (AUTHORITY, authority_account.clone()),
+        (system_program, system_account.clone()),
+    ];
+
+    mollusk.process_and_validate_instruction_chain(
+        &[
+            (&create, &[Check::success()]),
+            (
+                &increase,
+                &[
+                    Check::success(),
+                    Check::account(&counter_pda).data(&expected.data).build(),
+                ],
+            ),
+        ],
+        tx_accounts,
+    );
+}
+
+#[test]
+fn test_create_counter_with_oversized_seed_fails() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let (counter_pda, _bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let counter_account = Account::new(0, 0, &system_program);
+
+    let mut ix_data = vec![*Create::DISCRIMINATOR, 33];
+    ix_data.extend_from_slice(&[7; 33]);
+    let instruction = Instruction::new_with_bytes(
+        PROGRAM,
+        &ix_data,
+        vec![
+            AccountMeta::new(counter_pda, false),
+            AccountMeta::new(AUTHORITY, true),
+            AccountMeta::new_readonly(config_pda(), false),
+            AccountMeta::new_readonly(system_program, false),
+        ],
+    );
+
+    let tx_accounts = &[
+        keyed_config_account(),
+        (counter_pda, counter_account.clone()),
+        (AUTHORITY, authority_account.clone()),
+        (system_program, system_account.clone()),
+    ];
+
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        tx_accounts,
+        &[Check::err(counter_error(CounterError::InvalidSeed))],
+    );
+}
+
+#[test]
+fn test_increase_uninitialized_counter_fails() {
+    let mollusk = mollusk();
+
+    let (system_program, _system_account) = program::keyed_account_for_system_program();
+
+    let (counter_pda, _bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    // Program-owned and correctly sized, but never initialized
+    let counter_account = program_account(&mollusk, Counter::LEN);
+
+    let instruction = increase_instruction(counter_pda, AUTHORITY, true, 1);
+
+    let tx_accounts = &[
+        keyed_config_account(),
+        (counter_pda, counter_account.clone()),
+        (AUTHORITY, authority_account.clone()),
+    ];
+
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        tx_accounts,
+        &[Check::err(counter_error(CounterError::NotInitialized))],
+    );
+}
+
+#[test]
+fn test_counter_event_round_trip() {
+    let event = CounterEvent {
+        kind: EventKind::Increased,
+        counter_kind: StateKey::SignedCounter as u8,
+        counter: Pubkey::new_unique().to_bytes(),
+        authority: AUTHORITY.to_bytes(),
+        old_value: (-1i64).to_event_value(),
+        new_value: 1i64.to_event_value(),
+        slot: 1234,
+    };
+
+    let bytes = event.to_bytes();
+
+    assert_eq!(bytes[0], EventKind::Increased as u8);
+    assert_eq!(bytes[1], StateKey::SignedCounter as u8);
+    assert_eq!(CounterEvent::from_bytes(&bytes), Some(event));
+    // Signed values are sign-extended, so they read back as the same i128
+    assert_eq!(event.old_value as i128, -1);
+    assert_ne!(event.old_value, u64::MAX.to_event_value());
+    assert_eq!(u128::MAX.to_event_value(), u128::MAX);
+    // Truncated data and unknown kinds are rejected
+    assert_eq!(
+        CounterEvent::from_bytes(&bytes[..CounterEvent::LEN - 1]),
+        None
+    );
+    let mut unknown = bytes;
+    unknown[0] = u8::MAX;
+    assert_eq!(CounterEvent::from_bytes(&unknown), None);
+}
+
+#[test]
+fn test_config_address_matches_seeds() {
+    assert_eq!(
+        Pubkey::find_program_address(&Config::seeds(), &PROGRAM),
+        (Pubkey::new_from_array(Config::ADDRESS), Config::BUMP)
+    );
+}
+
+#[test]
+fn test_create_counter_with_prefunded_pda() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let (counter_pda, bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+
+    let rent = mollusk.sysvars.rent.minimum_balance(Counter::LEN);
+    let expected = counter_account(&mollusk, &AUTHORITY, bump, 0);
+
+    // Someone sent lamports to the PDA before it was created: both below and
+    // above the rent-exempt minimum.
+    for prefunded in [1, 10 * LAMPORTS_PER_SOL] {
+        let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+        let counter_account = Account::new(prefunded, 0, &system_program);
+
+        let instruction = create_instruction(*Create::DISCRIMINATOR, counter_pda, AUTHORITY);
+
+        let tx_accounts = &[
+            keyed_config_account(),
+            (counter_pda, counter_account.clone()),
+            (AUTHORITY, authority_account.clone()),
+            (system_program, system_account.clone()),
+        ];
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            tx_accounts,
+            &[
+                Check::success(),
+                Check::account(&counter_pda)
+                    .owner(&PROGRAM)
+                    .lamports(prefunded.max(rent))
+                    .data(&expected.data)
+                    .build(),
+                Check::account(&AUTHORITY)
+                    .lamports(LAMPORTS_PER_SOL - rent.saturating_sub(prefunded))
+                    .build(),
+            ],
+        );
+    }
+}
+
+#[test]
+fn test_create_idempotent_then_increase() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let (counter_pda, bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let expected = updated_counter_account(&mollusk, &AUTHORITY, bump, 5, 1);
+
+    let create = create_instruction(*CreateIdempotent::DISCRIMINATOR, counter_pda, AUTHORITY);
+    let increase = increase_instruction(counter_pda, AUTHORITY, true, 5);
+
+    let tx_accounts = &[
+        keyed_config_account(),
+        (counter_pda, Account::new(0, 0, &system_program)),
+        (AUTHORITY, authority_account.clone()),
+        (system_program, system_account.clone()),
+    ];
+
+    mollusk.process_and_validate_instruction_chain(
+        &[
+            (&create, &[Check::success()]),
+            (
+                &increase,
+                &[
+                    Check::success(),
+                    Check::account(&counter_pda).data(&expected.data).build(),
+                ],
+            ),
+        ],
+        tx_accounts,
+    );
+}
+
+#[test]
+fn test_create_idempotent_leaves_existing_counter_unchanged() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let (counter_pda, bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let counter_account = counter_account(&mollusk, &AUTHORITY, bump, 7);
+
+    let instruction = create_instruction(*CreateIdempotent::DISCRIMINATOR, counter_pda, AUTHORITY);
+
+    let tx_accounts = &[
+        keyed_config_account(),
+        (counter_pda, counter_account.clone()),
+        (AUTHORITY, authority_account.clone()),
+        (system_program, system_account.clone()),
+    ];
+
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        tx_accounts,
+        &[
+            Check::success(),
+            Check::account(&counter_pda)
+                .data(&counter_account.data)
+                .lamports(counter_account.lamports)
+                .build(),
+            Check::account(&AUTHORITY)
+                .lamports(LAMPORTS_PER_SOL)
+                .build(),
+        ],
+    );
+
+    // Another signer cannot claim the existing counter's address
+    let instruction = create_instruction(
+        *CreateIdempotent::DISCRIMINATOR,
+        counter_pda,
+        OTHER_AUTHORITY,
+    );
+
+    let tx_accounts = &[
+        keyed_config_account(),
+        (counter_pda, counter_account.clone()),
+        (OTHER_AUTHORITY, authority_account.clone()),
+        (system_program, system_account.clone()),
+    ];
+
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        tx_accounts,
+        &[Check::err(counter_error(CounterError::InvalidPda))],
+    );
+}
+
+#[test]
+fn test_compare_and_set_counter() {
+    let mollusk = mollusk();
+
+    let (system_program, _system_account) = program::keyed_account_for_system_program();
+
+    let (counter_pda, bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let expected = updated_counter_account(&mollusk, &AUTHORITY, bump, 42, 1);
+    let counter_account = counter_account(&mollusk, &AUTHORITY, bump, 7);
+
+    let instruction = authority_instruction(
+        *CompareAndSet::DISCRIMINATOR,
+        &[7u64.to_le_bytes(), 42u64.to_le_bytes()].concat(),
+        counter_pda,
+        AUTHORITY,
+        true,
+    );
+
+    let tx_accounts = &[
+        keyed_config_account(),
+        (counter_pda, counter_account.clone()),
+        (AUTHORITY, authority_account.clone()),
+    ];
+
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        tx_accounts,
+        &[
+            Check::success(),
+            Check::return_data(
+                &ValueChange {
+                    previous: 7,
+                    new: 42,
+                }
+                .to_bytes(),
+            ),
+            Check::account(&counter_pda).data(&expected.data).build(),
+        ],
+    );
+}
+
+#[test]
+fn test_compare_and_set_with_stale_value_fails() {
+    let mollusk = mollusk();
+
+    let (system_program, _system_account) = program::keyed_account_for_system_program();
+
+    let (counter_pda, bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let counter_account = counter_account(&mollusk, &AUTHORITY, bump, 8);
+
+    let instruction = authority_instruction(
+        *CompareAndSet::DISCRIMINATOR,
+        &[7u64.to_le_bytes(), 42u64.to_le_bytes()].concat(),
+        counter_pda,
+        AUTHORITY,
+        true,
+    );
+
+    let tx_accounts = &[
+        keyed_config_account(),
+        (counter_pda, counter_account.clone()),
+        (AUTHORITY, authority_account.clone()),
+    ];
+
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        tx_accounts,
+        &[Check::err(counter_error(CounterError::ValueMismatch))],
+    );
+}
+
+#[test]
+fn test_create_counter_with_limits() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let (counter_pda, bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let counter_account = Account::new(0, 0, &system_program);
+
+    let limits = Limits {
+        min: 10,
+        max: 20,
+        max_step: 5,
+    };
+    let expected = counter_account_with(&mollusk, |counter| {
+        counter.set_inner(AUTHORITY.to_bytes(), bump, &[]);
+        counter.set_limits(&limits);
+        counter.set_value(15);
+        counter.record_update(0, 0);
+    });
+
+    let mut ix_data = vec![*Create::DISCRIMINATOR, 0];
+    ix_data.extend_from_slice(&limits.to_bytes());
+    let create = Instruction::new_with_bytes(
+        PROGRAM,
+        &ix_data,
+        vec![
+            AccountMeta::new(counter_pda, false),
+            AccountMeta::new(AUTHORITY, true),
+            AccountMeta::new_readonly(config_pda(), false),
+            AccountMeta::new_readonly(system_program, false),
+        ],
+    );
+
+    let tx_accounts = &[
+        keyed_config_account(),
+        (counter_pda, counter_account.clone()),
+        (AUTHORITY, authority_account.clone()),
+        (system_program, system_account.clone()),
+    ];
+
+    // The counter starts at `min`
+    mollusk.process_and_validate_instruction_chain(
+        &[
+            (&create, &[Check::success()]),
+            (
+                &increase_instruction(counter_pda, AUTHORITY, true, 5),
+                &[
+                    Check::success(),
+                    Check::account(&counter_pda).data(&expected.data).build(),
+                ],
+            ),
+            (
+                &increase_instruction(counter_pda, AUTHORITY, true, 6),
+                &[Check::err(counter_error(CounterError::StepTooLarge))],
+            ),
+        ],
+        tx_accounts,
+    );
+}
+
+#[test]
+fn test_configure_counter_limits() {
+    let mollusk = mollusk();
+
+    let (system_program, _system_account) = program::keyed_account_for_system_program();
+
+    let (counter_pda, bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let counter_account = counter_account(&mollusk, &AUTHORITY, bump, 7);
+
+    let limits = Limits {
+        min: 5,
+        max: 10,
+        max_step: u64::MAX,
+    };
+    let expected = counter_account_with(&mollusk, |counter| {
+        counter.set_inner(AUTHORITY.to_bytes(), bump, &[]);
+        counter.set_limits(&limits);
+        counter.set_value(7);
+    });
+
+    let tx_accounts = &[
+        keyed_config_account(),
+        (counter_pda, counter_account.clone()),
+        (AUTHORITY, authority_account.clone()),
+    ];
+
+    let result = mollusk.process_and_validate_instruction(
+        &authority_instruction(
+            *Configure::DISCRIMINATOR,
+            &limits.to_bytes(),
+            counter_pda,
+            AUTHORITY,
+            true,
+        ),
+        tx_accounts,
+        &[
+            Check::success(),
+            Check::account(&counter_pda).data(&expected.data).build(),
+        ],
+    );
+
+    let tx_accounts = &[
+        keyed_config_account(),
+        (
+            counter_pda,
+            result.get_account(&counter_pda).unwrap().clone(),
+        ),
+        (AUTHORITY, authority_account.clone()),
+    ];
+
+    for (instruction, error) in [
+        (
+            increase_instruction(counter_pda, AUTHORITY, true, 4),
+            CounterError::AboveMaximum,
+        ),
+        (
+            decrease_instruction(counter_pda, AUTHORITY, true, 3),
+            CounterError::BelowMinimum,
+        ),
+        (
+            authority_instruction(
+                *Set::DISCRIMINATOR,
+                &11u64.to_le_bytes(),
+                counter_pda,
+                AUTHORITY,
+                true,
+            ),
+            CounterError::AboveMaximum,
+        ),
+        // New limits must contain the current value
+        (
+            authority_instruction(
+                *Configure::DISCRIMINATOR,
+                &Limits {
+                    min: 8,
+                    ..Limits::UNBOUNDED
+                }
+                .to_bytes(),
+                counter_pda,
+                AUTHORITY,
+                true,
+            ),
+            CounterError::BelowMinimum,
+        ),
+    ] {
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            tx_accounts,
+            &[Check::err(counter_error(error))],
+        );
+    }
+}
+
+#[test]
+fn test_frozen_counter_rejects_mutations_until_thawed() {
+    let mollusk = mollusk();
+
+    let (system_program, _system_account) = program::keyed_account_for_system_program();
+
+    let (counter_pda, bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let expected = updated_counter_account(&mollusk, &AUTHORITY, bump, 8, 1);
+    let counter_account = counter_account(&mollusk, &AUTHORITY, bump, 7);
+
+    let frozen = counter_account_with(&mollusk, |counter| {
+        counter.set_inner(AUTHORITY.to_bytes(), bump, &[]);
+        counter.set_value(7);
+        counter.set_status(CounterStatus::Frozen);
+    });
+
+    mollusk.process_and_validate_instruction(
+        &authority_instruction(*Freeze::DISCRIMINATOR, &[], counter_pda, AUTHORITY, true),
+        &[
+            keyed_config_account(),
+            (counter_pda, counter_account.clone()),
+            (AUTHORITY, authority_account.clone()),
+        ],
+        &[
+            Check::success(),
+            Check::account(&counter_pda).data(&frozen.data).build(),
+        ],
+    );
+
+    let tx_accounts = &[
+        keyed_config_account(),
+        (counter_pda, frozen.clone()),
+        (AUTHORITY, authority_account.clone()),
+    ];
+
+    for instruction in [
+        increase_instruction(counter_pda, AUTHORITY, true, 1),
+        decrease_instruction(counter_pda, AUTHORITY, true, 1),
+        authority_instruction(
+            *Set::DISCRIMINATOR,
+            &42u64.to_le_bytes(),
+            counter_pda,
+            AUTHORITY,
+            true,
+        ),
+        reset_instruction(counter_pda, AUTHORITY, true),
+        authority_instruction(
+            *ProposeAuthority::DISCRIMINATOR,
+            OTHER_AUTHORITY.as_ref(),
+            counter_pda,
+            AUTHORITY,
+            true,
+        ),
+        close_instruction(counter_pda, AUTHORITY, AUTHORITY),
+    ] {
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            tx_accounts,
+            &[Check::err(counter_error(CounterError::Frozen))],
+        );
+    }
+
+    mollusk.process_and_validate_instruction_chain(
+        &[
+            (
+                &authority_instruction(*Thaw::DISCRIMINATOR, &[], counter_pda, AUTHORITY, true),
+                &[Check::success()],
+            ),
+            (
+                &increase_instruction(counter_pda, AUTHORITY, true, 1),
+                &[
+                    Check::success(),
+                    Check::account(&counter_pda).data(&expected.data).build(),
+                ],
+            ),
+        ],
+        tx_accounts,
+    );
+}
+
+#[test]
+fn test_delegate_increases_within_allowance() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let (counter_pda, bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+    let (delegate_pda, delegate_bump) = Pubkey::find_program_address(
+        &Delegate::seeds(&counter_pda.to_bytes(), &OTHER_AUTHORITY.to_bytes()),
+        &PROGRAM,
+    );
+
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let expected = counter_account_with(&mollusk, |counter| {
+        counter.set_inner(AUTHORITY.to_bytes(), bump, &[]);
+        counter.set_value(10);
+        counter.record_update(0, 0);
+        counter.add_delegate().unwrap();
+    });
+    let counter_account = counter_account(&mollusk, &AUTHORITY, bump, 7);
+
+    let mut expected_delegate = program_account(&mollusk, Delegate::LEN);
+    Delegate::load_mut(&mut expected_delegate.data, false)
+        .unwrap()
+        .set_inner(
+            counter_pda.to_bytes(),
+            OTHER_AUTHORITY.to_bytes(),
+            delegate_bump,
+            2,
+            None,
+        );
+
+    let tx_accounts = &[
+        keyed_config_account(),
+        (counter_pda, counter_account.clone()),
+        (AUTHORITY, authority_account.clone()),
+        (OTHER_AUTHORITY, Account::new(0, 0, &system_program)),
+        (delegate_pda, Account::new(0, 0, &system_program)),
+        (system_program, system_account.clone()),
+    ];
+
+    let result = mollusk.process_and_validate_instruction_chain(
+        &[
+            (
+                &approve_delegate_instruction(
+                    counter_pda,
+                    AUTHORITY,
+                    true,
+                    AUTHORITY,
+                    OTHER_AUTHORITY,
+                    5,
+                    0,
+                ),
+                &[Check::success()],
+            ),
+            (
+                &delegate_increase_instruction(counter_pda, OTHER_AUTHORITY, 3),
+                &[
+                    Check::success(),
+                    Check::account(&counter_pda).data(&expected.data).build(),
+                    Check::account(&delegate_pda)
+                        .data(&expected_delegate.data)
+                        .build(),
+                ],
+            ),
+        ],
+        tx_accounts,
+    );
+
+    let tx_accounts = &[
+        keyed_config_account(),
+        (
+            counter_pda,
+            result.get_account(&counter_pda).unwrap().clone(),
+        ),
+        (AUTHORITY, result.get_account(&AUTHORITY).unwrap().clone()),
+        (OTHER_AUTHORITY, Account::new(0, 0, &system_program)),
+        (
+            delegate_pda,
+            result.get_account(&delegate_pda).unwrap().clone(),
+        ),
+        (system_program, system_account.clone()),
+    ];
+
+    mollusk.process_and_validate_instruction(
+        &delegate_increase_instruction(counter_pda, OTHER_AUTHORITY, 3),
+        tx_accounts,
+        &[Check::err(counter_error(CounterError::AllowanceExceeded))],
+    );
+
+    // The delegate cannot act as the authority
+    mollusk.process_and_validate_instruction(
+        &increase_instruction(counter_pda, OTHER_AUTHORITY, true, 1),
+        tx_accounts,
+        &[Check::err(counter_error(CounterError::InvalidAuthority))],
+    );
+
+    mollusk.process_and_validate_instruction(
+        &revoke_delegate_instruction(counter_pda, AUTHORITY, true, OTHER_AUTHORITY, AUTHORITY),
+        tx_accounts,
+        &[
+            Check::success(),
+            Check::account(&delegate_pda).closed().build(),
+            Check::account(&AUTHORITY)
+                .lamports(LAMPORTS_PER_SOL)
+                .build(),
+        ],
+    );
+}
+
+#[test]
+fn test_close_counter_with_delegates_fails() {
+    let mollusk = mollusk();
+
+    let (system_program, _system_account) = program::keyed_account_for_system_program();
+
+    let (counter_pda, bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let counter_account = counter_account_with(&mollusk, |counter| {
+        counter.set_inner(AUTHORITY.to_bytes(), bump, &[]);
+        counter.add_delegate().unwrap();
+    });
+
+    mollusk.process_and_validate_instruction(
+        &close_instruction(counter_pda, AUTHORITY, AUTHORITY),
+        &[
+            keyed_config_account(),
+            (counter_pda, counter_account),
+            (AUTHORITY, authority_account),
+        ],
+        &[Check::err(counter_error(
+            CounterError::DelegatesOutstanding,
+        ))],
+    );
+}
+
+#[test]
+fn test_expired_delegate_cannot_increase() {
+    let mut mollusk = mollusk();
+
+    let (system_program, _system_account) = program::keyed_account_for_system_program();
+
+    let (counter_pda, bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+    let (delegate_pda, delegate_bump) = Pubkey::find_program_address(
+        &Delegate::seeds(&counter_pda.to_bytes(), &OTHER_AUTHORITY.to_bytes()),
+        &PROGRAM,
+    );
+
+    let mut delegate_account = program_account(&mollusk, Delegate::LEN);
+    Delegate::load_mut(&mut delegate_account.data, false)
+        .unwrap()
+        .set_inner(
+            counter_pda.to_bytes(),
+            OTHER_AUTHORITY.to_bytes(),
+            delegate_bump,
+            100,
+            Some(50),
+        );
+
+    mollusk.warp_to_slot(51);
+
+    mollusk.process_and_validate_instruction(
+        &delegate_increase_instruction(counter_pda, OTHER_AUTHORITY, 1),
+        &[
+            keyed_config_account(),
+            (counter_pda, counter_account(&mollusk, &AUTHORITY, bump, 7)),
+            (OTHER_AUTHORITY, Account::new(0, 0, &system_program)),
+            (delegate_pda, delegate_account),
+        ],
+        &[Check::err(counter_error(CounterError::DelegateExpired))],
+    );
+}
+
+#[test]
+fn test_rate_limited_increase_fails_within_cooldown() {
+    let mut mollusk = mollusk();
+
+    let (system_program, _system_account) = program::keyed_account_for_system_program();
+
+    let (counter_pda, bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let counter_account = counter_account(&mollusk, &AUTHORITY, bump, 7);
+
+    let rate_limit = RateLimit {
+        cooldown_slots: 10,
+        cooldown_seconds: 0,
+        window_slots: 0,
+        window_max: 0,
+    };
+    let mut data = vec![*ConfigureRateLimit::DISCRIMINATOR];
+    data.extend_from_slice(&rate_limit.to_bytes());
+    let configure = Instruction::new_with_bytes(
+        PROGRAM,
+        &data,
+        vec![
+            AccountMeta::new(counter_pda, false),
+            AccountMeta::new(AUTHORITY, true),
+            AccountMeta::new_readonly(config_pda(), false),
+        ],
+    );
+
+    mollusk.warp_to_slot(100);
+
+    let tx_accounts = &[
+        keyed_config_account(),
+        (counter_pda, counter_account.clone()),
+        (AUTHORITY, authority_account.clone()),
+    ];
+
+    let result = mollusk.process_and_validate_instruction_chain(
+        &[
+            (&configure, &[Check::success()]),
+            (
+                &increase_instruction(counter_pda, AUTHORITY, true, 1),
+                &[Check::success()],
+            ),
+            (
+                &increase_instruction(counter_pda, AUTHORITY, true, 1),
+                &[Check::err(counter_error(CounterError::RateLimited))],
+            ),
+        ],
+        tx_accounts,
+    );
+
+    let counter_account = result.get_account(&counter_pda).unwrap().clone();
+    let counter = Counter::load(&counter_account.data).unwrap();
+    assert_eq!(counter.value(), 8);
+    assert_eq!(counter.last_updated_slot(), 100);
+
+    mollusk.warp_to_slot(110);
+
+    mollusk.process_and_validate_instruction(
+        &increase_instruction(counter_pda, AUTHORITY, true, 1),
+        &[
+            keyed_config_account(),
+            (counter_pda, counter_account.clone()),
+            (AUTHORITY, authority_account.clone()),
+        ],
+        &[Check::success()],
+    );
+}
+
+#[test]
+fn test_first_increase_after_create_is_not_rate_limited() {
+    let mut mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let (counter_pda, _bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+
+    let rate_limit = RateLimit {
+        cooldown_slots: 10,
+        cooldown_seconds: 0,
+        window_slots: 0,
+        window_max: 0,
+    };
+    let mut data = vec![*ConfigureRateLimit::DISCRIMINATOR];
+    data.extend_from_slice(&rate_limit.to_bytes());
+    let configure = Instruction::new_with_bytes(
+        PROGRAM,
+        &data,
+        vec![
+            AccountMeta::new(counter_pda, false),
+            AccountMeta::new(AUTHORITY, true),
+            AccountMeta::new_readonly(config_pda(), false),
+        ],
+    );
+
+    // Creation stamps the counter with this slot, which must not start a
+    // cooldown for the first increase
+    mollusk.warp_to_slot(100);
+
+    mollusk.process_and_validate_instruction_chain(
+        &[
+            (
+                &create_instruction(*Create::DISCRIMINATOR, counter_pda, AUTHORITY),
+                &[Check::success()],
+            ),
+            (&configure, &[Check::success()]),
+            (
+                &increase_instruction(counter_pda, AUTHORITY, true, 1),
+                &[Check::success()],
+            ),
+            (
+                &increase_instruction(counter_pda, AUTHORITY, true, 1),
+                &[Check::err(counter_error(CounterError::RateLimited))],
+            ),
+        ],
+        &[
+            keyed_config_account(),
+            (counter_pda, Account::new(0, 0, &system_program)),
+            (AUTHORITY, authority_account),
+            (system_program, system_account),
+        ],
+    );
+}
+
+#[test]
+fn test_migrate_v1_counter_to_v2() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let (counter_pda, bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+
+    let v1_rent = mollusk.sysvars.rent.minimum_balance(CounterV1::LEN);
+    let rent = mollusk.sysvars.rent.minimum_balance(Counter::LEN);
+
+    // `[key, bump, authority, padding, value]` as written by the original program
+    let mut v1_account = Account::new(v1_rent, CounterV1::LEN, &PROGRAM);
+    v1_account.data[0] = 1;
+    v1_account.data[1] = bump;
+    v1_account.data[2..34].copy_from_slice(AUTHORITY.as_ref());
+    v1_account.data[40..42].copy_from_slice(&300u16.to_le_bytes());
+
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let payer_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let expected = counter_account(&mollusk, &AUTHORITY, bump, 300);
+    assert_eq!(expected.data[1], Counter::VERSION);
+
+    // Version 1 counters must be migrated before they can be mutated
+    mollusk.process_and_validate_instruction(
+        &increase_instruction(counter_pda, AUTHORITY, true, 1),
+        &[
+            keyed_config_account(),
+            (counter_pda, v1_account.clone()),
+            (AUTHORITY, authority_account.clone()),
+        ],
+        &[Check::err(counter_error(CounterError::OutdatedVersion))],
+    );
+
+    // Migration keeps every field, so any payer may fund it
+    let instruction = Instruction::new_with_bytes(
+        PROGRAM,
+        &[*Migrate::DISCRIMINATOR],
+        vec![
+            AccountMeta::new(counter_pda, false),
+            AccountMeta::new(OTHER_AUTHORITY, true),
+            AccountMeta::new_readonly(config_pda(), false),
+            AccountMeta::new_readonly(system_program, false),
+        ],
+    );
+
+    let tx_accounts = &[
+        keyed_config_account(),
+        (counter_pda, v1_account.clone()),
+        (OTHER_AUTHORITY, payer_account.clone()),
+        (system_program, system_account.clone()),
+    ];
+
+    mollusk.process_and_validate_instruction_chain(
+        &[
+            (
+                &instruction,
+                &[
+                    Check::success(),
+                    Check::account(&counter_pda)
+                        .space(Counter::LEN)
+                        .lamports(rent)
+                        .data(&expected.data)
+                        .build(),
+                    Check::account(&OTHER_AUTHORITY)
+                        .lamports(LAMPORTS_PER_SOL - (rent - v1_rent))
+                        .build(),
+                ],
+            ),
+            // Migrating a current counter is a no-op
+            (
+                &instruction,
+                &[
+                    Check::success(),
+                    Check::account(&counter_pda)
+                        .lamports(rent)
+                        .data(&expected.data)
+                        .build(),
+                    Check::account(&OTHER_AUTHORITY)
+                        .lamports(LAMPORTS_PER_SOL - (rent - v1_rent))
+                        .build(),
+                ],
+            ),
+        ],
+        tx_accounts,
+    );
+}
+
+#[test]
+fn test_initialize_config_requires_upgrade_authority() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+    let loader = Pubkey::new_from_array(ProgramDataAccount::LOADER_ID);
+    let (program_data, _) = Pubkey::find_program_address(&[PROGRAM.as_ref()], &loader);
+
+    // `UpgradeableLoaderState::ProgramData` header with AUTHORITY as the
+    // upgrade authority
+    let mut program_data_account = Account::new(LAMPORTS_PER_SOL, 45, &loader);
+    program_data_account.data[0..4].copy_from_slice(&3u32.to_le_bytes());
+    program_data_account.data[12] = 1;
+    program_data_account.data[13..45].copy_from_slice(AUTHORITY.as_ref());
+
+    let instruction = |upgrade_authority: Pubkey| {
+        let mut data = vec![*InitializeConfig::DISCRIMINATOR];
+        data.extend_from_slice(OTHER_AUTHORITY.as_ref());
+        data.extend_from_slice(&10u64.to_le_bytes());
+        Instruction::new_with_bytes(
+            PROGRAM,
+            &data,
+            vec![
+                AccountMeta::new(config_pda(), false),
+                AccountMeta::new(upgrade_authority, true),
+                AccountMeta::new_readonly(program_data, false),
+                AccountMeta::new_readonly(system_program, false),
+            ],
+        )
+    };
+
+    let tx_accounts = &[
+        (config_pda(), Account::new(0, 0, &system_program)),
+        (
+            AUTHORITY,
+            Account::new(LAMPORTS_PER_SOL, 0, &system_program),
+        ),
+        (
+            OTHER_AUTHORITY,
+            Account::new(LAMPORTS_PER_SOL, 0, &system_program),
+        ),
+        (program_data, program_data_account.clone()),
+        (system_program, system_account.clone()),
+    ];
+
+    mollusk.process_and_validate_instruction(
+        &instruction(OTHER_AUTHORITY),
+        tx_accounts,
+        &[Check::err(counter_error(CounterError::InvalidAuthority))],
+    );
+
+    let expected = config_account(&mollusk, &OTHER_AUTHORITY, false, 10);
+    mollusk.process_and_validate_instruction(
+        &instruction(AUTHORITY),
+        tx_accounts,
+        &[
+            Check::success(),
+            Check::account(&config_pda())
+                .owner(&PROGRAM)
+                .data(&expected.data)
+                .build(),
+        ],
+    );
+}
+
+#[test]
+fn test_paused_config_blocks_mutations() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let (counter_pda, bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let counter_account = counter_account(&mollusk, &AUTHORITY, bump, 7);
+    let paused_config = config_account(&mollusk, &AUTHORITY, true, u64::MAX);
+
+    let tx_accounts = &[
+        (config_pda(), paused_config.clone()),
+        (counter_pda, counter_account.clone()),
+        (AUTHORITY, authority_account.clone()),
+        (system_program, system_account.clone()),
+    ];
+
+    for instruction in [
+        increase_instruction(counter_pda, AUTHORITY, true, 1),
+        decrease_instruction(counter_pda, AUTHORITY, true, 1),
+        reset_instruction(counter_pda, AUTHORITY, true),
+        authority_instruction(*Freeze::DISCRIMINATOR, &[], counter_pda, AUTHORITY, true),
+    ] {
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            tx_accounts,
+            &[Check::err(counter_error(CounterError::Paused))],
+        );
+    }
+
+    // Only the admin can lift the pause, after which mutations go through
+    mollusk.process_and_validate_instruction(
+        &update_config_instruction(OTHER_AUTHORITY, AUTHORITY, false, u64::MAX),
+        &[
+            (config_pda(), paused_config.clone()),
+            (OTHER_AUTHORITY, Account::new(0, 0, &system_program)),
+        ],
+        &[Check::err(counter_error(CounterError::InvalidAuthority))],
+    );
+
+    let unpaused_config = config_account(&mollusk, &AUTHORITY, false, u64::MAX);
+    mollusk.process_and_validate_instruction_chain(
+        &[
+            (
+                &update_config_instruction(AUTHORITY, AUTHORITY, false, u64::MAX),
+                &[
+                    Check::success(),
+                    Check::account(&config_pda())
+                        .data(&unpaused_config.data)
+                        .build(),
+                ],
+            ),
+            (
+                &increase_instruction(counter_pda, AUTHORITY, true, 1),
+                &[Check::success()],
+            ),
+        ],
+        tx_accounts,
+    );
+}
+
+#[test]
+fn test_create_counter_with_config_default_max_step() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let (counter_pda, bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+
+    let expected = counter_account_with(&mollusk, |counter| {
+        counter.set_inner(AUTHORITY.to_bytes(), bump, &[]);
+        counter.set_limits(&Limits {
+            max_step: 5,
+            ..Limits::UNBOUNDED
+        });
+    });
+
+    mollusk.process_and_validate_instruction(
+        &create_instruction(*Create::DISCRIMINATOR, counter_pda, AUTHORITY),
+        &[
+            (config_pda(), config_account(&mollusk, &AUTHORITY, false, 5)),
+            (counter_pda, Account::new(0, 0, &system_program)),
+            (
+                AUTHORITY,
+                Account::new(LAMPORTS_PER_SOL, 0, &system_program),
+            ),
+            (system_program, system_account.clone()),
+        ],
+        &[
+            Check::success(),
+            Check::account(&counter_pda).data(&expected.data).build(),
+        ],
+    );
+}
+
+#[test]
+fn test_initialize_multisig() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+    let (multisig_pda, _) =
+        Pubkey::find_program_address(&Multisig::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+
+    let instruction = |m: u8| {
+        Instruction::new_with_bytes(
+            PROGRAM,
+            &[*InitializeMultisig::DISCRIMINATOR, m],
+            vec![
+                AccountMeta::new(multisig_pda, false),
+                AccountMeta::new(AUTHORITY, true),
+                AccountMeta::new_readonly(system_program, false),
+                AccountMeta::new_readonly(AUTHORITY, false),
+                AccountMeta::new_readonly(OTHER_AUTHORITY, false),
+            ],
+        )
+    };
+
+    let tx_accounts = &[
+        (multisig_pda, Account::new(0, 0, &system_program)),
+        (
+            AUTHORITY,
+            Account::new(LAMPORTS_PER_SOL, 0, &system_program),
+        ),
+        (OTHER_AUTHORITY, Account::new(0, 0, &system_program)),
+        (system_program, system_account.clone()),
+    ];
+
+    mollusk.process_and_validate_instruction(
+        &instruction(3),
+        tx_accounts,
+        &[Check::err(counter_error(CounterError::InvalidMultisig))],
+    );
+
+    let expected = multisig_account(&mollusk, &AUTHORITY, 2, &[AUTHORITY, OTHER_AUTHORITY]);
+    mollusk.process_and_validate_instruction(
+        &instruction(2),
+        tx_accounts,
+        &[
+            Check::success(),
+            Check::account(&multisig_pda)
+                .owner(&PROGRAM)
+                .data(&expected.data)
+                .build(),
+        ],
+    );
+}
+
+#[test]
+fn test_multisig_authority_requires_m_signers() {
+    let mollusk = mollusk();
+
+    let (system_program, _) = program::keyed_account_for_system_program();
+    let third = Pubkey::new_unique();
+    let members = [AUTHORITY, OTHER_AUTHORITY, third];
+
+    let (counter_pda, bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+    let (multisig_pda, _) =
+        Pubkey::find_program_address(&Multisig::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+
+    // The counter was created by AUTHORITY and handed over to a 2-of-3 multisig
+    let counter_account = counter_account_with(&mollusk, |counter| {
+        counter.set_inner(AUTHORITY.to_bytes(), bump, &[]);
+        counter.authority = multisig_pda.to_bytes();
+        counter.set_value(7);
+    });
+    let expected = counter_account_with(&mollusk, |counter| {
+        counter.set_inner(AUTHORITY.to_bytes(), bump, &[]);
+        counter.authority = multisig_pda.to_bytes();
+        counter.record_update(0, 0);
+    });
+
+    let tx_accounts = &[
+        keyed_config_account(),
+        (counter_pda, counter_account.clone()),
+        (
+            multisig_pda,
+            multisig_account(&mollusk, &AUTHORITY, 2, &members),
+        ),
+        (
+            AUTHORITY,
+            Account::new(LAMPORTS_PER_SOL, 0, &system_program),
+        ),
+        (OTHER_AUTHORITY, Account::new(0, 0, &system_program)),
+        (third, Account::new(0, 0, &system_program)),
+    ];
+
+    // A single member, even listed twice, cannot reset the counter
+    mollusk.process_and_validate_instruction(
+        &with_multisig_signers(
+            reset_instruction(counter_pda, multisig_pda, false),
+            &[AUTHORITY, AUTHORITY],
+        ),
+        tx_accounts,
+        &[Check::err(counter_error(CounterError::NotEnoughSigners))],
+    );
+
+    // Neither can the key that created it
+    mollusk.process_and_validate_instruction(
+        &reset_instruction(counter_pda, AUTHORITY, true),
+        tx_accounts,
+        &[Check::err(counter_error(CounterError::InvalidAuthority))],
+    );
+
+    mollusk.process_and_validate_instruction(
+        &with_multisig_signers(
+            reset_instruction(counter_pda, multisig_pda, false),
+            &[OTHER_AUTHORITY, third],
+        ),
+        tx_accounts,
+        &[
+            Check::success(),
+            Check::account(&counter_pda).data(&expected.data).build(),
+        ],
+    );
+}
+
+#[test]
+fn test_multisig_authority_approves_and_revokes_delegate() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+    let members = [AUTHORITY, OTHER_AUTHORITY];
+    let delegate = Pubkey::new_unique();
+    let payer = Pubkey::new_unique();
+
+    let (counter_pda, bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+    let (multisig_pda, _) =
+        Pubkey::find_program_address(&Multisig::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+    let (delegate_pda, _) = Pubkey::find_program_address(
+        &Delegate::seeds(&counter_pda.to_bytes(), &delegate.to_bytes()),
+        &PROGRAM,
+    );
+
+    let counter_account = counter_account_with(&mollusk, |counter| {
+        counter.set_inner(AUTHORITY.to_bytes(), bump, &[]);
+        counter.authority = multisig_pda.to_bytes();
+    });
+    let delegate_rent = mollusk.sysvars.rent.minimum_balance(Delegate::LEN);
+
+    mollusk.process_and_validate_instruction_chain(
+        &[
+            (
+                &with_multisig_signers(
+                    approve_delegate_instruction(
+                        counter_pda,
+                        multisig_pda,
+                        false,
+                        payer,
+                        delegate,
+                        5,
+                        0,
+                    ),
+                    &members,
+                ),
+                &[
+                    Check::success(),
+                    Check::account(&payer)
+                        .lamports(LAMPORTS_PER_SOL - delegate_rent)
+                        .build(),
+                ],
+            ),
+            (
+                &with_multisig_signers(
+                    revoke_delegate_instruction(counter_pda, multisig_pda, false, delegate, payer),
+                    &members,
+                ),
+                &[
+                    Check::success(),
+                    Check::account(&delegate_pda).closed().build(),
+                    Check::account(&payer).lamports(LAMPORTS_PER_SOL).build(),
+                ],
+            ),
+        ],
+        &[
+            keyed_config_account(),
+            (counter_pda, counter_account),
+            (
+                multisig_pda,
+                multisig_account(&mollusk, &AUTHORITY, 2, &members),
+            ),
+            (delegate_pda, Account::new(0, 0, &system_program)),
+            (payer, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+            (system_program, system_account),
+            (AUTHORITY, Account::new(0, 0, &system_program)),
+            (OTHER_AUTHORITY, Account::new(0, 0, &system_program)),
+        ],
+    );
+}
+
+#[test]
+fn test_signed_counter_goes_below_zero() {
+    let mut mollusk = mollusk();
+    mollusk.warp_to_slot(100);
+    let unix_timestamp = mollusk.sysvars.clock.unix_timestamp;
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let (counter_pda, bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let counter_account = Account::new(0, 0, &system_program);
+
+    // Creation stamps the counter without counting an update
+    let mut created = vec![0; SignedCounter::LEN];
+    let counter = SignedCounter::load_mut(&mut created, false).unwrap();
+    counter.set_inner(AUTHORITY.to_bytes(), bump, &[]);
+    counter.touch(100, unix_timestamp);
+
+    let mut expected = created.clone();
+    let counter = SignedCounter::load_mut(&mut expected, false).unwrap();
+    counter.set_value(-3);
+    counter.record_update(100, unix_timestamp);
+    counter.record_update(100, unix_timestamp);
+
+    let create = create_instruction(*CreateSigned::DISCRIMINATOR, counter_pda, AUTHORITY);
+    let decrease = decrease_instruction(counter_pda, AUTHORITY, true, 5);
+    let increase = increase_instruction(counter_pda, AUTHORITY, true, 2);
+
+    let tx_accounts = &[
+        keyed_config_account(),
+        (counter_pda, counter_account.clone()),
+        (AUTHORITY, authority_account.clone()),
+        (system_program, system_account.clone()),
+    ];
+
+    let result = mollusk.process_and_validate_instruction_chain(
+        &[
+            (
+                &create,
+                &[
+                    Check::success(),
+                    Check::account(&counter_pda).data(&created).build(),
+                ],
+            ),
+            (&decrease, &[Check::success()]),
+            (
+                &increase,
+                &[
+                    Check::success(),
+                    Check::account(&counter_pda).data(&expected).build(),
+                ],
+            ),
+        ],
+        tx_accounts,
+    );
+
+    assert_eq!(
+        SignedValueChange::from_bytes(&result.return_data),
+        Some(SignedValueChange {
+            previous: -5,
+            new: -3
+        })
+    );
+}
+
+#[test]
+fn test_frozen_signed_counter_rejects_increase() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let (counter_pda, _bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let counter_account = Account::new(0, 0, &system_program);
+
+    let create = create_instruction(*CreateSigned::DISCRIMINATOR, counter_pda, AUTHORITY);
+    let freeze = authority_instruction(*Freeze::DISCRIMINATOR, &[], counter_pda, AUTHORITY, true);
+    let increase = increase_instruction(counter_pda, AUTHORITY, true, 1);
+
+    mollusk.process_and_validate_instruction_chain(
+        &[
+            (&create, &[Check::success()]),
+            (&freeze, &[Check::success()]),
+            (
+                &increase,
+                &[Check::err(counter_error(CounterError::Frozen))],
+            ),
+        ],
+        &[
+            keyed_config_account(),
+            (counter_pda, counter_account),
+            (AUTHORITY, authority_account),
+            (system_program, system_account),
+        ],
+    );
+}
+
+#[test]
+fn test_wide_counter_grows_past_u64() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let (counter_pda, bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let counter_account = Account::new(0, 0, &system_program);
+
+    let mut expected = vec![0; WideCounter::LEN];
+    let counter = WideCounter::load_mut(&mut expected, false).unwrap();
+    counter.set_inner(AUTHORITY.to_bytes(), bump, &[]);
+    counter.set_value(2 * u64::MAX as u128);
+    counter.record_update(0, 0);
+    counter.record_update(0, 0);
+
+    let create = create_instruction(*CreateWide::DISCRIMINATOR, counter_pda, AUTHORITY);
+    let increase = increase_instruction(counter_pda, AUTHORITY, true, u64::MAX);
+
+    let tx_accounts = &[
+        keyed_config_account(),
+        (counter_pda, counter_account.clone()),
+        (AUTHORITY, authority_account.clone()),
+        (system_program, system_account.clone()),
+    ];
+
+    let result = mollusk.process_and_validate_instruction_chain(
+        &[
+            (&create, &[Check::success()]),
+            (&increase, &[Check::success()]),
+            (
+                &increase,
+                &[
+                    Check::success(),
+                    Check::account(&counter_pda).data(&expected).build(),
+                ],
+            ),
+        ],
+        tx_accounts,
+    );
+
+    assert_eq!(
+        WideValueChange::from_bytes(&result.return_data),
+        Some(WideValueChange {
+            previous: u64::MAX as u128,
+            new: 2 * u64::MAX as u128
+        })
+    );
+}
+
+#[test]
+fn test_reset_signed_counter_returns_to_zero() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let (counter_pda, _bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let counter_account = Account::new(0, 0, &system_program);
+
+    let create = create_instruction(*CreateSigned::DISCRIMINATOR, counter_pda, AUTHORITY);
+
+    let result = mollusk.process_and_validate_instruction_chain(
+        &[
+            (&create, &[Check::success()]),
+            (
+                &decrease_instruction(counter_pda, AUTHORITY, true, 5),
+                &[Check::success()],
+            ),
+            (
+                &reset_instruction(counter_pda, AUTHORITY, true),
+                &[Check::success()],
+            ),
+        ],
+        &[
+            keyed_config_account(),
+            (counter_pda, counter_account),
+            (AUTHORITY, authority_account),
+            (system_program, system_account),
+        ],
+    );
+
+    assert_eq!(
+        SignedValueChange::from_bytes(&result.return_data),
+        Some(SignedValueChange {
+            previous: -5,
+            new: 0
+        })
+    );
+}
+
+#[test]
+fn test_batch_increase_counters() {
+    let mollusk = mollusk();
+
+    let (system_program, _system_account) = program::keyed_account_for_system_program();
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+
+    let counters: Vec<(Pubkey, Account)> = (0..3)
+        .map(|index| indexed_counter(&mollusk, &AUTHORITY, index, 10, 0))
+        .collect();
+    let keys: Vec<Pubkey> = counters.iter().map(|(key, _)| *key).collect();
+
+    let instruction = batch_increase_instruction(&keys, AUTHORITY, &[1, 2, 3]);
+
+    let mut tx_accounts = counters.clone();
+    tx_accounts.push((AUTHORITY, authority_account));
+    tx_accounts.push(keyed_config_account());
+
+    let expected: Vec<Account> = (0..3)
+        .map(|index| indexed_counter(&mollusk, &AUTHORITY, index, 11 + index, 1).1)
+        .collect();
+    let mut checks = vec![Check::success()];
+    checks.extend(
+        keys.iter()
+            .zip(&expected)
+            .map(|(key, expected)| Check::account(key).data(&expected.data).build()),
+    );
+
+    let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &checks);
+
+    assert_eq!(
+        BatchValues::from_bytes(&result.return_data),
+        Some(vec![11, 12, 13])
+    );
+}
+
+#[test]
+fn test_batch_increase_is_atomic() {
+    let mollusk = mollusk();
+
+    let (system_program, _system_account) = program::keyed_account_for_system_program();
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+
+    let (first, first_account) = indexed_counter(&mollusk, &AUTHORITY, 0, 0, 0);
+    // The second counter cannot take the increase, so neither is applied
+    let (second, second_account) = indexed_counter(&mollusk, &AUTHORITY, 1, u64::MAX, 0);
+
+    let instruction = batch_increase_instruction(&[first, second], AUTHORITY, &[1, 1]);
+
+    let tx_accounts = &[
+        (first, first_account.clone()),
+        (second, second_account),
+        (AUTHORITY, authority_account.clone()),
+        keyed_config_account(),
+    ];
+
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        tx_accounts,
+        &[Check::err(counter_error(CounterError::ArithmeticOverflow))],
+    );
+
+    // Every amount needs a counter
+    let instruction = batch_increase_instruction(&[first], AUTHORITY, &[1, 1]);
+    let tx_accounts = &[
+        (first, first_account),
+        (AUTHORITY, authority_account),
+        keyed_config_account(),
+    ];
+
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        tx_accounts,
+        &[Check::err(ProgramError::NotEnoughAccountKeys)],
+    );
+}
+
+#[test]
+fn test_create_increment_and_reset_tally() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let seed = b"poll";
+    let (tally_pda, expected_account) = tally_account(&mollusk, &AUTHORITY, seed, &[0, 5, 0]);
+    let (_, expected_reset_account) = tally_account(&mollusk, &AUTHORITY, seed, &[0, 0, 0]);
+
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let tally_account = Account::new(0, 0, &system_program);
+
+    let create = create_tally_instruction(tally_pda, AUTHORITY, 3, seed);
+    let increment = |index: u16, amount: u64| {
+        increment_tally_instruction(tally_pda, AUTHORITY, true, index, amount)
+    };
+    let reset = reset_tally_instruction(tally_pda, AUTHORITY, true);
+
+    let tx_accounts = &[
+        keyed_config_account(),
+        (tally_pda, tally_account.clone()),
+        (AUTHORITY, authority_account.clone()),
+        (system_program, system_account.clone()),
+    ];
+
+    mollusk.process_and_validate_instruction_chain(
+        &[
+            (
+                &create,
+                &[
+                    Check::success(),
+                    Check::account(&tally_pda)
+                        .space(Tally::space(3))
+                        .owner(&PROGRAM)
+                        .build(),
+                ],
+            ),
+            (
+                &increment(1, 5),
+                &[
+                    Check::success(),
+                    Check::return_data(
+                        &ValueChange {
+                            previous: 0,
+                            new: 5,
+                        }
+                        .to_bytes(),
+                    ),
+                    Check::account(&tally_pda)
+                        .data(&expected_account.data)
+                        .build(),
+                ],
+            ),
+            (
+                &reset,
+                &[
+                    Check::success(),
+                    Check::account(&tally_pda)
+                        .data(&expected_reset_account.data)
+                        .build(),
+                ],
+            ),
+            (
+                &increment(3, 1),
+                &[Check::err(counter_error(CounterError::InvalidSlot))],
+            ),
+        ],
+        tx_accounts,
+    );
+}
+
+#[test]
+fn test_create_existing_tally_fails() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let seed = b"poll";
+    let (tally_pda, tally_account) = tally_account(&mollusk, &AUTHORITY, seed, &[0, 7, 0]);
+
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+
+    let instruction = create_tally_instruction(tally_pda, AUTHORITY, 3, seed);
+
+    let tx_accounts = &[
+        keyed_config_account(),
+        (tally_pda, tally_account.clone()),
+        (AUTHORITY, authority_account.clone()),
+        (system_program, system_account.clone()),
+    ];
+
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        tx_accounts,
+        &[Check::err(counter_error(CounterError::AlreadyInitialized))],
+    );
+}
+
+#[test]
+fn test_increment_tally_without_authority_signature_fails() {
+    let mollusk = mollusk();
+
+    let (system_program, _system_account) = program::keyed_account_for_system_program();
+
+    let (tally_pda, tally_account) = tally_account(&mollusk, &AUTHORITY, b"poll", &[0, 0, 0]);
+
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+
+    // Authority is passed but does not sign
+    let instruction = increment_tally_instruction(tally_pda, AUTHORITY, false, 0, 1);
+
+    let tx_accounts = &[
+        keyed_config_account(),
+        (tally_pda, tally_account.clone()),
+        (AUTHORITY, authority_account.clone()),
+    ];
+
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        tx_accounts,
+        &[Check::err(ProgramError::MissingRequiredSignature)],
+    );
+}
+
+#[test]
+fn test_increment_tally_with_other_signer_fails() {
+    let mollusk = mollusk();
+
+    let (system_program, _system_account) = program::keyed_account_for_system_program();
+
+    let (tally_pda, tally_account) = tally_account(&mollusk, &AUTHORITY, b"poll", &[0, 0, 0]);
+
+    let other_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+
+    // A different key signs in place of the tally authority
+    let instruction = increment_tally_instruction(tally_pda, OTHER_AUTHORITY, true, 0, 1);
+
+    let tx_accounts = &[
+        keyed_config_account(),
+        (tally_pda, tally_account.clone()),
+        (OTHER_AUTHORITY, other_account.clone()),
+    ];
+
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        tx_accounts,
+        &[Check::err(counter_error(CounterError::InvalidAuthority))],
+    );
+}
+
+#[test]
+fn test_increment_tally_overflow_fails() {
+    let mollusk = mollusk();
+
+    let (system_program, _system_account) = program::keyed_account_for_system_program();
+
+    let (tally_pda, tally_account) =
+        tally_account(&mollusk, &AUTHORITY, b"poll", &[0, u64::MAX, 0]);
+
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+
+    let instruction = increment_tally_instruction(tally_pda, AUTHORITY, true, 1, 1);
+
+    let tx_accounts = &[
+        keyed_config_account(),
+        (tally_pda, tally_account.clone()),
+        (AUTHORITY, authority_account.clone()),
+    ];
+
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        tx_accounts,
+        &[
+            Check::err(counter_error(CounterError::ArithmeticOverflow)),
+            Check::account(&tally_pda).data(&tally_account.data).build(),
+        ],
+    );
+}
+
+#[test]
+fn test_reset_tally_by_non_authority_fails() {
+    let mollusk = mollusk();
+
+    let (system_program, _system_account) = program::keyed_account_for_system_program();
+
+    let (tally_pda, tally_account) = tally_account(&mollusk, &AUTHORITY, b"poll", &[3, 0, 9]);
+
+    let other_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+
+    // A different key signs in place of the tally authority
+    let instruction = reset_tally_instruction(tally_pda, OTHER_AUTHORITY, true);
+
+    let tx_accounts = &[
+        keyed_config_account(),
+        (tally_pda, tally_account.clone()),
+        (OTHER_AUTHORITY, other_account.clone()),
+    ];
+
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        tx_accounts,
+        &[
+            Check::err(counter_error(CounterError::InvalidAuthority)),
+            Check::account(&tally_pda).data(&tally_account.data).build(),
+        ],
+    );
+}
+
+#[test]
+fn test_history_records_counter_changes() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let (counter_pda, bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+    let (history_pda, history_bump) =
+        Pubkey::find_program_address(&History::seeds(&counter_pda.to_bytes()), &PROGRAM);
+    let (other_counter_pda, other_counter_account) = indexed_counter(&mollusk, &AUTHORITY, 1, 0, 0);
+
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let counter_account = counter_account(&mollusk, &AUTHORITY, bump, 7);
+
+    let mut expected = vec![0; History::LEN];
+    let history = History::load_mut(&mut expected, false).unwrap();
+    history.set_inner(counter_pda.to_bytes(), history_bump);
+    history.append(HistoryEntry::new(0, 3, 10));
+    history.append(HistoryEntry::new(0, -5, 5));
+    history.append(HistoryEntry::new(0, 15, 20));
+    history.append(HistoryEntry::new(0, 2, 22));
+
+    let initialize = initialize_history_instruction(counter_pda, AUTHORITY, true, AUTHORITY);
+
+    let tx_accounts = &[
+        keyed_config_account(),
+        (counter_pda, counter_account.clone()),
+        (other_counter_pda, other_counter_account.clone()),
+        (AUTHORITY, authority_account.clone()),
+        (history_pda, Account::new(0, 0, &system_program)),
+        (system_program, system_account.clone()),
+    ];
+
+    mollusk.process_and_validate_instruction_chain(
+        &[
+            (
+                &initialize,
+                &[
+                    Check::success(),
+                    Check::account(&history_pda)
+                        .space(History::LEN)
+                        .owner(&PROGRAM)
+                        .build(),
+                ],
+            ),
+            (
+                &with_history(
+                    increase_instruction(counter_pda, AUTHORITY, true, 3),
+                    counter_pda,
+                ),
+                &[Check::success()],
+            ),
+            (
+                &with_history(
+                    decrease_instruction(counter_pda, AUTHORITY, true, 5),
+                    counter_pda,
+                ),
+                &[Check::success()],
+            ),
+            // Once the counter has a history, every change has to be recorded
+            (
+                &increase_instruction(counter_pda, AUTHORITY, true, 1),
+                &[Check::err(counter_error(CounterError::HistoryRequired))],
+            ),
+            (
+                &batch_increase_instruction(&[counter_pda], AUTHORITY, &[1]),
+                &[Check::err(counter_error(CounterError::HistoryRequired))],
+            ),
+            (
+                &with_history(
+                    authority_instruction(
+                        *Set::DISCRIMINATOR,
+                        &20u64.to_le_bytes(),
+                        counter_pda,
+                        AUTHORITY,
+                        true,
+                    ),
+                    counter_pda,
+                ),
+                &[Check::success()],
+            ),
+            // A batch records each counter whose history follows it
+            (
+                &with_history(
+                    batch_increase_instruction(
+                        &[counter_pda, other_counter_pda],
+                        AUTHORITY,
+                        &[2, 1],
+                    ),
+                    counter_pda,
+                ),
+                &[
+                    Check::success(),
+                    Check::account(&history_pda).data(&expected).build(),
+                ],
+            ),
+            // A counter cannot record to another counter's history
+            (
+                &with_history(
+                    increase_instruction(other_counter_pda, AUTHORITY, true, 1),
+                    counter_pda,
+                ),
+                &[Check::err(counter_error(CounterError::InvalidHistory))],
+            ),
+        ],
+        tx_accounts,
+    );
+}
+
+/// Builds the history of `counter` holding one recorded change.
+fn history_account(mollusk: &Mollusk, counter: &Pubkey) -> (Pubkey, Account) {
+    let (history_pda, history_bump) =
+        Pubkey::find_program_address(&History::seeds(&counter.to_bytes()), &PROGRAM);
+
+    let mut history_account = program_account(mollusk, History::LEN);
+    let history = History::load_mut(&mut history_account.data, false).unwrap();
+    history.set_inner(counter.to_bytes(), history_bump);
+    history.append(HistoryEntry::new(0, 1, 8));
+    (history_pda, history_account)
+}
+
+#[test]
+fn test_close_counter_closes_history() {
+    let mollusk = mollusk();
+
+    let (system_program, _system_account) = program::keyed_account_for_system_program();
+
+    let (counter_pda, bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+    let (history_pda, history_account) = history_account(&mollusk, &counter_pda);
+
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let counter_account = counter_account_with(&mollusk, |counter| {
+        counter.set_inner(AUTHORITY.to_bytes(), bump, &[]);
+        counter.set_value(8);
+        counter.set_has_history(true);
+    });
+
+    let tx_accounts = &[
+        keyed_config_account(),
+        (counter_pda, counter_account),
+        (AUTHORITY, authority_account),
+        (history_pda, history_account),
+    ];
+
+    // The history would otherwise outlive the counter
+    mollusk.process_and_validate_instruction(
+        &close_instruction(counter_pda, AUTHORITY, AUTHORITY),
+        tx_accounts,
+        &[Check::err(counter_error(CounterError::HistoryRequired))],
+    );
+
+    mollusk.process_and_validate_instruction(
+        &with_history(
+            close_instruction(counter_pda, AUTHORITY, AUTHORITY),
+            counter_pda,
+        ),
+        tx_accounts,
+        &[
+            Check::success(),
+            Check::account(&counter_pda).closed().build(),
+            Check::account(&history_pda).closed().build(),
+        ],
+    );
+}
+
+#[test]
+fn test_initialize_history_resets_leftover_history() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let (counter_pda, bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+    // Left behind by an earlier counter at the same address
+    let (history_pda, history_account) = history_account(&mollusk, &counter_pda);
+    let (_, history_bump) =
+        Pubkey::find_program_address(&History::seeds(&counter_pda.to_bytes()), &PROGRAM);
+
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let counter_account = counter_account(&mollusk, &AUTHORITY, bump, 0);
+
+    let mut expected = vec![0; History::LEN];
+    History::load_mut(&mut expected, false)
+        .unwrap()
+        .set_inner(counter_pda.to_bytes(), history_bump);
+
+    let initialize = initialize_history_instruction(counter_pda, AUTHORITY, true, AUTHORITY);
+
+    mollusk.process_and_validate_instruction_chain(
+        &[
+            (
+                &initialize,
+                &[
+                    Check::success(),
+                    Check::account(&history_pda).data(&expected).build(),
+                ],
+            ),
+            // A counter that has a history cannot reset it
+            (
+                &initialize,
+                &[Check::err(counter_error(CounterError::AlreadyInitialized))],
+            ),
+        ],
+        &[
+            keyed_config_account(),
+            (counter_pda, counter_account),
+            (AUTHORITY, authority_account),
+            (history_pda, history_account),
+            (system_program, system_account),
+        ],
+    );
+}
+
+#[test]
+fn test_multisig_authority_initializes_history() {
+    let mollusk = mollusk();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+    let members = [AUTHORITY, OTHER_AUTHORITY];
+    let payer = Pubkey::new_unique();
+
+    let (counter_pda, bump) =
+        Pubkey::find_program_address(&Counter::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+    let (multisig_pda, _) =
+        Pubkey::find_program_address(&Multisig::seeds(&AUTHORITY.to_bytes(), &[]), &PROGRAM);
+    let (history_pda, history_bump) =
+        Pubkey::find_program_address(&History::seeds(&counter_pda.to_bytes()), &PROGRAM);
+
+    let counter_account = counter_account_with(&mollusk, |counter| {
+        counter.set_inner(AUTHORITY.to_bytes(), bump, &[]);
+        counter.authority = multisig_pda.to_bytes();
+    });
+
+    let mut expected = vec![0; History::LEN];
+    let history = History::load_mut(&mut expected, false).unwrap();
+    history.set_inner(counter_pda.to_bytes(), history_bump);
+    history.append(HistoryEntry::new(0, 4, 4));
+
+    mollusk.process_and_validate_instruction_chain(
+        &[
+            (
+                &with_multisig_signers(
+                    initialize_history_instruction(counter_pda, multisig_pda, false, payer),
+                    &members,
+                ),
+                &[
+                    Check::success(),
+                    Check::account(&payer)
+                        .lamports(
+                            LAMPORTS_PER_SOL - mollusk.sysvars.rent.minimum_balance(History::LEN),
+                        )
+                        .build(),
+                ],
+            ),
+            (
+                &with_multisig_signers(
+                    with_history(
+                        increase_instruction(counter_pda, multisig_pda, false, 4),
+                        counter_pda,
+                    ),
+                    &members,
+                ),
+                &[
+                    Check::success(),
+                    Check::account(&history_pda).data(&expected).build(),
+                ],
+            ),
+        ],
+        &[
+            keyed_config_account(),
+            (counter_pda, counter_account),
+            (
+                multisig_pda,
+                multisig_account(&mollusk, &AUTHORITY, 2, &members),
+            ),
+            (history_pda, Account::new(0, 0, &system_program)),
+            (payer, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+            (system_program, system_account),
+            (AUTHORITY, Account::new(0, 0, &system_program)),
+            (OTHER_AUTHORITY, Account::new(0, 0, &system_program)),
+        ],
+    );
 }